@@ -29,6 +29,13 @@ pub enum Event {
 
         /// The text produced by the key press, if any.
         text: Option<SmolStr>,
+
+        /// Whether the key press was produced by holding the key down.
+        ///
+        /// Key repetition follows the rate and delay configured by the
+        /// platform. Ignore these events to disable it; for instance, for
+        /// game input.
+        repeat: bool,
     },
 
     /// A keyboard key was released.
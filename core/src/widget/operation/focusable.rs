@@ -173,6 +173,9 @@ where
 
 /// Produces an [`Operation`] that searches for the current focused widget
 /// and stores its ID. This ignores widgets that do not have an ID.
+///
+/// The result is a snapshot of the widget tree and may be outdated as soon
+/// as the next event is processed.
 pub fn find_focused() -> impl Operation<Id> {
     struct FindFocused {
         focused: Option<Id>,
@@ -6,6 +6,7 @@ mod event;
 mod id;
 mod level;
 mod mode;
+mod monitor;
 mod position;
 mod redraw_request;
 mod user_attention;
@@ -15,6 +16,7 @@ pub use icon::Icon;
 pub use id::Id;
 pub use level::Level;
 pub use mode::Mode;
+pub use monitor::Monitor;
 pub use position::Position;
 pub use redraw_request::RedrawRequest;
 pub use settings::Settings;
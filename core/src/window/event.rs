@@ -42,6 +42,16 @@ pub enum Event {
     /// A window was unfocused.
     Unfocused,
 
+    /// A window was fully hidden from view or became visible again.
+    ///
+    /// The `bool` is `true` when the window is occluded. While occluded, the
+    /// window stops redrawing; applications may want to pause any animations.
+    ///
+    /// ## Platform-specific
+    /// - **Wayland:** Sent when the compositor suspends the surface.
+    /// - **X11 / Android / iOS:** Unsupported.
+    Occluded(bool),
+
     /// A file is being hovered over the window.
     ///
     /// When the user hovers multiple files at once, this event will be emitted
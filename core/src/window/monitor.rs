@@ -0,0 +1,26 @@
+use crate::{Point, Size};
+
+/// A monitor where windows can be displayed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    /// The human-readable name of the [`Monitor`], if known.
+    ///
+    /// This is the most durable identifier available; but it names the
+    /// connector, not the physical monitor. On Wayland, it is the name of the
+    /// output advertised by the compositor (e.g. `DP-1`), which stays the
+    /// same across reconnections to the same port.
+    pub name: Option<String>,
+
+    /// The position of the top-left corner of the [`Monitor`] in the
+    /// desktop, in physical pixels.
+    pub position: Point,
+
+    /// The resolution of the [`Monitor`], in physical pixels.
+    pub size: Size,
+
+    /// The scale factor of the [`Monitor`].
+    pub scale_factor: f32,
+
+    /// The refresh rate of the [`Monitor`] in millihertz, if known.
+    pub refresh_rate: Option<u32>,
+}
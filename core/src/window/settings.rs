@@ -24,7 +24,7 @@ mod platform;
 #[path = "settings/other.rs"]
 mod platform;
 
-use crate::window::{Icon, Level, Position};
+use crate::window::{Icon, Id, Level, Position};
 use crate::Size;
 
 pub use platform::PlatformSpecific;
@@ -34,6 +34,18 @@ pub struct Settings {
     /// The initial logical dimensions of the window.
     pub size: Size,
 
+    /// The fraction of the monitor the window should cover initially, if
+    /// any; overriding the [`size`](Self::size) of the window.
+    ///
+    /// The monitor is the one of the most recently opened window or, if
+    /// there is none, the primary monitor. Wayland has no notion of a
+    /// primary monitor, so the first monitor advertised by the compositor
+    /// is used instead.
+    ///
+    /// The fraction must be greater than `0.0` and at most `1.0`. Any other
+    /// value is ignored and the [`size`](Self::size) is used instead.
+    pub monitor_fraction: Option<f32>,
+
     /// The initial position of the window.
     pub position: Position,
 
@@ -61,6 +73,12 @@ pub struct Settings {
     /// The icon of the window.
     pub icon: Option<Icon>,
 
+    /// The [`Id`] of the parent window, if any.
+    ///
+    /// A child window is stacked above its parent by the windowing system
+    /// and it is closed together with it. This is useful for dialogs.
+    pub parent: Option<Id>,
+
     /// Platform specific settings.
     pub platform_specific: PlatformSpecific,
 
@@ -79,6 +97,7 @@ impl Default for Settings {
     fn default() -> Self {
         Self {
             size: Size::new(1024.0, 768.0),
+            monitor_fraction: None,
             position: Position::default(),
             min_size: None,
             max_size: None,
@@ -88,6 +107,7 @@ impl Default for Settings {
             transparent: false,
             level: Level::default(),
             icon: None,
+            parent: None,
             exit_on_close_request: true,
             platform_specific: PlatformSpecific::default(),
         }
@@ -5,7 +5,7 @@ pub use screenshot::Screenshot;
 
 use crate::core::time::Instant;
 use crate::core::window::{
-    Event, Icon, Id, Level, Mode, Settings, UserAttention,
+    Event, Icon, Id, Level, Mode, Monitor, Settings, UserAttention,
 };
 use crate::core::{Point, Size};
 use crate::futures::event;
@@ -15,7 +15,7 @@ use crate::task::{self, Task};
 
 pub use raw_window_handle;
 
-use raw_window_handle::WindowHandle;
+use raw_window_handle::{DisplayHandle, WindowHandle};
 
 /// An operation to be performed on some window.
 #[allow(missing_debug_implementations)]
@@ -74,12 +74,32 @@ pub enum Action {
     /// Change the [`Mode`] of the window.
     ChangeMode(Id, Mode),
 
+    /// Get the monitors available to the window.
+    GetMonitors(Id, oneshot::Sender<Vec<Monitor>>),
+
+    /// Make the window fullscreen on the monitor with the given name.
+    ///
+    /// If no name is provided, or no monitor with such name exists, the
+    /// current monitor of the window will be used.
+    ///
+    /// ## Platform-specific
+    /// - **Wayland:** The name is the one advertised by the compositor for
+    ///   the output (e.g. `DP-1`).
+    FullscreenOn(Id, Option<String>),
+
     /// Get the current [`Mode`] of the window.
     GetMode(Id, oneshot::Sender<Mode>),
 
     /// Toggle the window to maximized or back
     ToggleMaximize(Id),
 
+    /// Lock the aspect ratio (width / height) of the window, or unlock it.
+    ///
+    /// The ratio is enforced by the client: every time the window is
+    /// resized, it is shrunk to the largest size with the locked ratio
+    /// that fits.
+    LockAspectRatio(Id, Option<f32>),
+
     /// Toggle whether window has decorations.
     ///
     /// ## Platform-specific
@@ -123,6 +143,12 @@ pub enum Action {
     /// Android / iOS / macOS / Orbital / Web / X11: Unsupported.
     ShowSystemMenu(Id),
 
+    /// Show the system menu at the given logical coordinates of the window.
+    ///
+    /// ## Platform-specific
+    /// Android / iOS / macOS / Orbital / Web / X11: Unsupported.
+    ShowSystemMenuAt(Id, Point),
+
     /// Get the raw identifier unique to the window.
     GetRawId(Id, oneshot::Sender<u64>),
 
@@ -145,6 +171,10 @@ pub enum Action {
     /// Runs the closure with the native window handle of the window with the given [`Id`].
     RunWithHandle(Id, Box<dyn FnOnce(WindowHandle<'_>) + Send>),
 
+    /// Runs the closure with the native display handle of the window with
+    /// the given [`Id`].
+    RunWithDisplayHandle(Id, Box<dyn FnOnce(DisplayHandle<'_>) + Send>),
+
     /// Screenshot the viewport of the window.
     Screenshot(Id, oneshot::Sender<Screenshot>),
 
@@ -324,6 +354,25 @@ pub fn change_mode<T>(id: Id, mode: Mode) -> Task<T> {
     task::effect(crate::Action::Window(Action::ChangeMode(id, mode)))
 }
 
+/// Gets the monitors available to the window with the given [`Id`].
+///
+/// Monitors can be connected and disconnected at any time; so the list
+/// should be fetched again before using it.
+pub fn monitors(id: Id) -> Task<Vec<Monitor>> {
+    task::oneshot(move |channel| {
+        crate::Action::Window(Action::GetMonitors(id, channel))
+    })
+}
+
+/// Makes the window fullscreen on the monitor with the given name.
+///
+/// If no name is provided, the current monitor of the window will be used.
+/// The names of the available monitors can be obtained with [`monitors`].
+/// Use [`change_mode`] with [`Mode::Windowed`] to leave fullscreen.
+pub fn fullscreen_on<T>(id: Id, monitor: Option<String>) -> Task<T> {
+    task::effect(crate::Action::Window(Action::FullscreenOn(id, monitor)))
+}
+
 /// Gets the current [`Mode`] of the window.
 pub fn get_mode(id: Id) -> Task<Mode> {
     task::oneshot(move |channel| {
@@ -336,6 +385,14 @@ pub fn toggle_maximize<T>(id: Id) -> Task<T> {
     task::effect(crate::Action::Window(Action::ToggleMaximize(id)))
 }
 
+/// Locks the aspect ratio (width / height) of the window, or unlocks it
+/// if `None`.
+///
+/// The ratio is not enforced while the window is maximized or fullscreen.
+pub fn lock_aspect_ratio<T>(id: Id, ratio: Option<f32>) -> Task<T> {
+    task::effect(crate::Action::Window(Action::LockAspectRatio(id, ratio)))
+}
+
 /// Toggles the window decorations.
 pub fn toggle_decorations<T>(id: Id) -> Task<T> {
     task::effect(crate::Action::Window(Action::ToggleDecorations(id)))
@@ -379,6 +436,18 @@ pub fn show_system_menu<T>(id: Id) -> Task<T> {
     task::effect(crate::Action::Window(Action::ShowSystemMenu(id)))
 }
 
+/// Show the [system menu] at the given logical coordinates of the window.
+///
+/// This can be used by custom title bars to open the menu on right click,
+/// at the position of the click.
+///
+/// [system menu]: https://en.wikipedia.org/wiki/Common_menus_in_Microsoft_Windows#System_menu
+pub fn show_system_menu_at<T>(id: Id, position: Point) -> Task<T> {
+    task::effect(crate::Action::Window(Action::ShowSystemMenuAt(
+        id, position,
+    )))
+}
+
 /// Gets an identifier unique to the window, provided by the underlying windowing system. This is
 /// not to be confused with [`Id`].
 pub fn get_raw_id<Message>(id: Id) -> Task<u64> {
@@ -412,6 +481,30 @@ where
     })
 }
 
+/// Runs the given callback with the native display handle for the window with the given id.
+///
+/// On Wayland, this is the `wl_display` the window's surface belongs to.
+/// The handle is only guaranteed to be valid during the callback; it must not
+/// be stored beyond it.
+///
+/// Note that if the window closes before this call is processed the callback will not be run.
+pub fn run_with_display_handle<T>(
+    id: Id,
+    f: impl FnOnce(DisplayHandle<'_>) -> T + Send + 'static,
+) -> Task<T>
+where
+    T: Send + 'static,
+{
+    task::oneshot(move |channel| {
+        crate::Action::Window(Action::RunWithDisplayHandle(
+            id,
+            Box::new(move |handle| {
+                let _ = channel.send(f(handle));
+            }),
+        ))
+    })
+}
+
 /// Captures a [`Screenshot`] from the window.
 pub fn screenshot(id: Id) -> Task<Screenshot> {
     task::oneshot(move |channel| {
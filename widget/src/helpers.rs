@@ -1818,6 +1818,18 @@ pub fn focus_next<T>() -> Task<T> {
     task::effect(Action::widget(operation::focusable::focus_next()))
 }
 
+/// Produces the [`Id`] of the focused widget, if any.
+///
+/// Widgets without an [`Id`] are ignored. The result is a snapshot and may be
+/// outdated as soon as the next event is processed.
+///
+/// [`Id`]: core::widget::Id
+pub fn focused() -> Task<Option<core::widget::Id>> {
+    task::widget(operation::focusable::find_focused())
+        .collect()
+        .map(|ids| ids.into_iter().next())
+}
+
 /// A container intercepting mouse events.
 pub fn mouse_area<'a, Message, Theme, Renderer>(
     widget: impl Into<Element<'a, Message, Theme, Renderer>>,
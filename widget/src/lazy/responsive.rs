@@ -1,21 +1,39 @@
+//! Build widgets that are aware of their dimensions.
+use crate::core::clipboard;
 use crate::core::event::{self, Event};
+use crate::core::keyboard;
 use crate::core::layout::{self, Layout};
 use crate::core::mouse;
 use crate::core::overlay;
 use crate::core::renderer;
-use crate::core::widget;
+use crate::core::time::{Duration, Instant};
+use crate::core::touch;
+use crate::core::widget::operation::scrollable::{self, RelativeOffset};
+use crate::core::widget::operation::{self, Operation};
 use crate::core::widget::tree::{self, Tree};
+use crate::core::widget::{self, Widget};
+use crate::core::window;
 use crate::core::{
-    self, Clipboard, Element, Length, Point, Rectangle, Shell, Size, Vector,
-    Widget,
+    self, Clipboard, Element, Length, Pixels, Point, Rectangle, Shell, Size,
+    Transformation, Vector,
 };
 use crate::horizontal_space;
+use crate::lazy::Lazy;
+use crate::runtime::futures::futures::channel::mpsc;
+use crate::runtime::futures::futures::stream::{self, StreamExt};
+use crate::runtime::futures::Subscription;
 use crate::runtime::overlay::Nested;
+use crate::runtime::task::{self, Task};
 
 use ouroboros::self_referencing;
-use std::cell::{RefCell, RefMut};
+use rustc_hash::{FxHashMap, FxHasher};
+use std::any::Any;
+use std::cell::{Cell, RefCell, RefMut};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Mutex;
 
 /// A widget that is aware of its dimensions.
 ///
@@ -29,8 +47,28 @@ pub struct Responsive<
     Theme = crate::Theme,
     Renderer = crate::Renderer,
 > {
-    view: Box<dyn Fn(Size) -> Element<'a, Message, Theme, Renderer> + 'a>,
+    view:
+        Box<dyn Fn(Size) -> Option<Element<'a, Message, Theme, Renderer>> + 'a>,
     content: RefCell<Content<'a, Message, Theme, Renderer>>,
+    id: Option<Id>,
+    explicit_limits: bool,
+    reserve: Size,
+    clip: bool,
+    frame_budget: Option<Duration>,
+    overlay_anchor: Anchor,
+    stretch_mode: StretchMode,
+    isolated_clipboard: bool,
+    stable_during_drag: bool,
+    stable_during_interaction: bool,
+    interactive: bool,
+    close_overlay_on_escape: bool,
+    on_first_build: Option<Box<dyn Fn(Size) -> Message + 'a>>,
+    on_overlay_change: Option<Box<dyn Fn(bool) -> Message + 'a>>,
+    on_content_resize: Option<Box<dyn Fn(Size) -> Message + 'a>>,
+    on_overflow: Option<Box<dyn Fn(Size) -> Message + 'a>>,
+    natural_height: Cell<Option<(f32, f32)>>,
+    size_estimate: Option<Box<dyn Fn(Size) -> Size + 'a>>,
+    measurement_cache: Option<(MeasurementCache, u64)>,
 }
 
 impl<'a, Message, Theme, Renderer> Responsive<'a, Message, Theme, Renderer>
@@ -47,20 +85,520 @@ where
         view: impl Fn(Size) -> Element<'a, Message, Theme, Renderer> + 'a,
     ) -> Self {
         Self {
-            view: Box::new(view),
+            view: Box::new(move |size| Some(view(size))),
             content: RefCell::new(Content {
                 size: Size::ZERO,
+                min: Size::ZERO,
                 layout: None,
                 element: Element::new(horizontal_space().width(0)),
+                is_stale: None,
+                scrollbar_reservation: 0.0,
+                max_depth: None,
+                is_empty: true,
+                retained: Vec::new(),
+                debug_name: None,
+                preserved_scroll: None,
+                pending_scroll: None,
+                fallback: None,
             }),
+            id: None,
+            explicit_limits: false,
+            reserve: Size::ZERO,
+            clip: false,
+            frame_budget: None,
+            overlay_anchor: Anchor::TopLeft,
+            stretch_mode: StretchMode::Keep,
+            isolated_clipboard: false,
+            stable_during_drag: false,
+            stable_during_interaction: true,
+            interactive: true,
+            close_overlay_on_escape: false,
+            on_first_build: None,
+            on_overlay_change: None,
+            on_content_resize: None,
+            on_overflow: None,
+            natural_height: Cell::new(None),
+            size_estimate: None,
+            measurement_cache: None,
+        }
+    }
+
+    /// Creates a new [`Responsive`] widget that separates the decision of
+    /// what to build from the building itself.
+    ///
+    /// The `decide` closure will be provided with the current [`Size`] of
+    /// the [`Responsive`] and should be cheap; for instance, choosing between
+    /// a single-line and a multi-line layout. The `view` closure will only be
+    /// called when the decision changes. Otherwise, the existing contents are
+    /// simply laid out again with the new [`Size`].
+    pub fn measured<D>(
+        decide: impl Fn(Size) -> D + 'a,
+        view: impl Fn(&D) -> Element<'a, Message, Theme, Renderer> + 'a,
+    ) -> Self
+    where
+        D: PartialEq + 'a,
+    {
+        let decide = Rc::new(decide);
+
+        let mut responsive = Self::new({
+            let decide = decide.clone();
+
+            move |size| view(&decide(size))
+        });
+
+        responsive.content.get_mut().is_stale =
+            Some(Box::new(move |old_size, new_size| {
+                decide(old_size) != decide(new_size)
+            }));
+
+        responsive
+    }
+
+    /// Creates a new [`Responsive`] widget with a closure that may produce
+    /// no contents at all.
+    ///
+    /// When the `view` closure returns `None`, the contents of the
+    /// [`Responsive`] will be empty; they will not be laid out, drawn, nor
+    /// will they react to any events. This can be used to hide parts of an
+    /// interface at certain sizes.
+    pub fn optional(
+        view: impl Fn(Size) -> Option<Element<'a, Message, Theme, Renderer>> + 'a,
+    ) -> Self {
+        let mut responsive = Self::new(|_| horizontal_space().width(0).into());
+        responsive.view = Box::new(view);
+
+        responsive
+    }
+
+    /// Creates a new [`Responsive`] widget with the given [`Id`] and a
+    /// closure that produces its contents.
+    ///
+    /// This is a shorthand for calling [`Responsive::new`] followed by
+    /// [`Responsive::id`].
+    pub fn with_id(
+        id: Id,
+        view: impl Fn(Size) -> Element<'a, Message, Theme, Renderer> + 'a,
+    ) -> Self {
+        Self::new(view).id(id)
+    }
+
+    /// Sets the [`Id`] of the [`Responsive`].
+    ///
+    /// The [`Id`] belongs to the [`Responsive`] itself, independently of its
+    /// contents; operations can use it to target the widget.
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets whether the contents of the [`Responsive`] should be laid out
+    /// using the minimum size enforced by its parent.
+    ///
+    /// By default, the contents are laid out with no minimum size.
+    pub fn explicit_limits(mut self, explicit_limits: bool) -> Self {
+        self.explicit_limits = explicit_limits;
+        self
+    }
+
+    /// Sets the minimum [`Size`] the [`Responsive`] will reserve, even if its
+    /// contents are empty or its parent offers less space.
+    ///
+    /// This can be used to avoid layout shifts while the contents are not
+    /// available yet.
+    pub fn reserve(mut self, size: impl Into<Size>) -> Self {
+        self.reserve = size.into();
+        self
+    }
+
+    /// Sets whether the contents of the [`Responsive`] should be clipped to
+    /// its bounds.
+    ///
+    /// When enabled, the contents will not draw outside of the bounds of
+    /// the [`Responsive`] and the cursor will be ignored when outside of them.
+    pub fn clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Sets the frame budget of the [`Responsive`].
+    ///
+    /// When the contents need to be rebuilt for a new size and the current
+    /// frame has already taken longer than the budget, the rebuild will be
+    /// deferred to the next frame and the old contents will be drawn in the
+    /// meantime. A rebuild is never deferred for two frames in a row.
+    ///
+    /// The budget is checked while handling a redraw request; so a size that
+    /// changes right before drawing, like when the window itself is resized,
+    /// is never deferred. Only a [`Responsive`] kept between views, like one
+    /// created with [`lazy`], avoids calling the view closure altogether;
+    /// otherwise the old contents are built again for their old size.
+    pub fn frame_budget(mut self, budget: Duration) -> Self {
+        self.frame_budget = Some(budget);
+        self
+    }
+
+    /// Sets the [`StretchMode`] of the [`Responsive`]; that is, how its stale
+    /// contents are drawn while a rebuild is deferred.
+    ///
+    /// See [`Responsive::frame_budget`].
+    pub fn stretch_mode(mut self, stretch_mode: StretchMode) -> Self {
+        self.stretch_mode = stretch_mode;
+        self
+    }
+
+    /// Sets the [`Anchor`] of the overlays opened by the contents of the
+    /// [`Responsive`].
+    ///
+    /// The overlays will be translated from the top-left corner of the
+    /// [`Responsive`] to the given [`Anchor`]. For instance, a menu can be
+    /// opened at the bottom edge of the region with [`Anchor::BottomLeft`].
+    pub fn overlay_anchor(mut self, anchor: Anchor) -> Self {
+        self.overlay_anchor = anchor;
+        self
+    }
+
+    /// Sets whether the contents of the [`Responsive`] should use their own
+    /// in-memory clipboard, isolated from the system one.
+    ///
+    /// When enabled, copying inside the [`Responsive`] will not reach the
+    /// system clipboard and pasting will only produce what was copied inside
+    /// of it. Useful for sandboxed previews.
+    pub fn isolated_clipboard(mut self, isolated: bool) -> Self {
+        self.isolated_clipboard = isolated;
+        self
+    }
+
+    /// Sets whether the contents of the [`Responsive`] should stay as they
+    /// are while files are being dragged over the window.
+    ///
+    /// When enabled, the contents keep the size they had when the drag
+    /// entered the window, even if the view is rebuilt in the meantime; so
+    /// drop targets do not shift under the cursor. Resizing resumes once the
+    /// files are dropped or the drag leaves the window. Disabled by default.
+    pub fn stable_during_drag(mut self, stable: bool) -> Self {
+        self.stable_during_drag = stable;
+        self
+    }
+
+    /// Sets whether the contents of the [`Responsive`] should stay as they
+    /// are while the user is interacting with them.
+    ///
+    /// An interaction starts when a child captures a mouse button press or
+    /// a touch; like when dragging a slider or selecting text. Any rebuild
+    /// is deferred until the button or finger is released, so the
+    /// interaction is not interrupted by a reflow. The contents keep their
+    /// size even if the interaction produces messages that rebuild the view.
+    /// The interaction also ends if the cursor leaves the window or the
+    /// window loses focus, since the release may never arrive then.
+    /// Enabled by default.
+    pub fn stable_during_interaction(mut self, stable: bool) -> Self {
+        self.stable_during_interaction = stable;
+        self
+    }
+
+    /// Sets whether the contents of the [`Responsive`] should react to user
+    /// input.
+    ///
+    /// A non-interactive [`Responsive`] is still drawn, but it lets all input
+    /// pass through to the widgets behind it and opens no overlays; which is
+    /// useful for purely decorative regions. Redraw requests still reach the
+    /// contents, so they can keep animating. Enabled by default.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Sets the width that should always be reserved for a vertical
+    /// scrollbar in the [`Size`] provided to the view closure.
+    ///
+    /// Contents that only need a scrollbar at some sizes can otherwise
+    /// alternate between the two layouts, since showing the scrollbar makes
+    /// the contents narrower.
+    pub fn reserve_scrollbar(mut self, width: impl Into<Pixels>) -> Self {
+        self.content.get_mut().scrollbar_reservation = width.into().0;
+        self
+    }
+
+    /// Sets the maximum amount of [`Responsive`] widgets that can be nested
+    /// inside each other, including this one.
+    ///
+    /// When the limit is reached, the contents will be replaced by an empty
+    /// placeholder instead of being built; guarding against runaway nesting.
+    /// The limit also applies to every [`Responsive`] nested inside, unless
+    /// they set a smaller one.
+    pub fn limit_depth(mut self, max_depth: usize) -> Self {
+        self.content.get_mut().max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets a human-readable name for the [`Responsive`] to identify it in
+    /// logs and in the [`Inspection`] of its contents.
+    pub fn debug_name(mut self, name: &'static str) -> Self {
+        self.content.get_mut().debug_name = Some(name);
+        self
+    }
+
+    /// Keeps the relative scroll offset of the scrollable with the given
+    /// [`widget::Id`] in the contents when they are rebuilt.
+    ///
+    /// The offset is relative to the scrollable area, so it is clamped
+    /// naturally if the height of the new contents changes.
+    pub fn preserve_scroll(mut self, id: impl Into<widget::Id>) -> Self {
+        self.content.get_mut().preserved_scroll = Some(id.into());
+        self
+    }
+
+    /// Sets the closure used to build the contents of the [`Responsive`]
+    /// when they cannot be built by the `view` closure.
+    ///
+    /// The closure will be provided with the [`FallbackReason`]. When unset,
+    /// the contents are left empty in these cases.
+    pub fn on_fallback(
+        mut self,
+        fallback: impl Fn(FallbackReason) -> Element<'a, Message, Theme, Renderer>
+            + 'a,
+    ) -> Self {
+        self.content.get_mut().fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Sets the identifiers of the [`retained`] children whose state must be
+    /// kept when the contents are rebuilt, even if they change position.
+    ///
+    /// Before every rebuild, the whole widget tree of the previous contents
+    /// is traversed looking for these identifiers; so keep the list short
+    /// and prefer keyed widgets when many children need to keep their state.
+    pub fn retain(mut self, ids: impl IntoIterator<Item = widget::Id>) -> Self {
+        self.content.get_mut().retained = ids.into_iter().collect();
+        self
+    }
+
+    /// Sets whether an overlay opened by the contents of the [`Responsive`]
+    /// should be closed when the Escape key is pressed and no widget handles
+    /// it.
+    ///
+    /// Only the overlay is affected: it is hidden right away and the Escape
+    /// key is captured. The widget that opened the overlay stays open until
+    /// the next press, which closes it the same way a click outside of it
+    /// would; so that press is captured as well, although it still reaches
+    /// every other widget in the contents. Disabled by default, since some
+    /// widgets may rely on the Escape key internally.
+    pub fn close_overlay_on_escape(mut self, close: bool) -> Self {
+        self.close_overlay_on_escape = close;
+        self
+    }
+
+    /// Sets the message that should be produced the first time the contents
+    /// of the [`Responsive`] are built with a non-zero [`Size`].
+    ///
+    /// The message is only produced once during the lifetime of the widget.
+    pub fn on_first_build(
+        mut self,
+        on_first_build: impl Fn(Size) -> Message + 'a,
+    ) -> Self {
+        self.on_first_build = Some(Box::new(on_first_build));
+        self
+    }
+
+    /// Sets the message that should be produced when an overlay opened by
+    /// the contents of the [`Responsive`] opens or closes.
+    ///
+    /// The closure will be provided with whether an overlay is open. The
+    /// message is produced with the first event processed after the change.
+    pub fn on_overlay_change(
+        mut self,
+        on_overlay_change: impl Fn(bool) -> Message + 'a,
+    ) -> Self {
+        self.on_overlay_change = Some(Box::new(on_overlay_change));
+        self
+    }
+
+    /// Sets the message that should be produced when the laid out size of
+    /// the contents of the [`Responsive`] changes.
+    ///
+    /// Unlike the bounds of the [`Responsive`]—which always fill its
+    /// parent—this is the intrinsic size of the contents; which may change
+    /// because they grow or shrink on their own. The message is produced
+    /// with the first event processed after the change.
+    pub fn on_content_resize(
+        mut self,
+        on_content_resize: impl Fn(Size) -> Message + 'a,
+    ) -> Self {
+        self.on_content_resize = Some(Box::new(on_content_resize));
+        self
+    }
+
+    /// Sets the message that should be produced when the contents of the
+    /// [`Responsive`] overflow its bounds or stop doing so.
+    ///
+    /// The closure will be provided with the amount of overflow along each
+    /// axis; which will be zero once the contents fit again. Overflows
+    /// smaller than a pixel are ignored and the message is only produced
+    /// when the amount changes; so apps can switch to a more compact layout
+    /// without being flooded with messages.
+    pub fn on_overflow(
+        mut self,
+        on_overflow: impl Fn(Size) -> Message + 'a,
+    ) -> Self {
+        self.on_overflow = Some(Box::new(on_overflow));
+        self
+    }
+
+    /// Returns the natural height of the contents of the [`Responsive`] at
+    /// the given `width`.
+    ///
+    /// The contents are built and laid out with an unbounded height using a
+    /// separate [`Tree`], so the live contents are not disturbed. The result
+    /// for the last `width` is cached.
+    ///
+    /// This can be used by parents that lay out their children in two
+    /// passes; asking for a height given a width.
+    pub fn height_for_width(&self, renderer: &Renderer, width: f32) -> f32 {
+        if let Some(estimate) = &self.size_estimate {
+            return estimate(Size::new(width, f32::INFINITY)).height;
+        }
+
+        if let Some((cached_width, height)) = self.natural_height.get() {
+            if cached_width == width {
+                return height;
+            }
+        }
+
+        let shared_key = self
+            .measurement_cache
+            .as_ref()
+            .map(|(cache, key)| (cache, (*key, width.to_bits())));
+
+        let cached_height =
+            shared_key.and_then(|(cache, key)| cache.get(key, Instant::now()));
+
+        let height = cached_height.unwrap_or_else(|| {
+            let height = self.measure(renderer, width);
+
+            if let Some((cache, key)) = shared_key {
+                cache.insert(key, height, Instant::now());
+            }
+
+            height
+        });
+
+        self.natural_height.set(Some((width, height)));
+
+        height
+    }
+
+    /// Sets a cheap estimate of the size of the contents of the
+    /// [`Responsive`] given the maximum [`Size`] available.
+    ///
+    /// When set, [`Responsive::height_for_width`] uses the estimate instead of
+    /// building the contents; so parents that measure their children in
+    /// several passes do not pay for a full build each time. The contents are
+    /// still built at the size they are finally given.
+    ///
+    /// The estimate should be conservative. If it is wrong, the parent will
+    /// allocate the wrong space and the contents will be laid out again to
+    /// fit it.
+    pub fn size_estimate(
+        mut self,
+        estimate: impl Fn(Size) -> Size + 'a,
+    ) -> Self {
+        self.size_estimate = Some(Box::new(estimate));
+        self
+    }
+
+    /// Sets the [`MeasurementCache`] of the [`Responsive`], shared with other
+    /// widgets, along with the key that identifies its contents.
+    ///
+    /// [`Responsive`] widgets with the same `key` must produce the same
+    /// contents; so their natural heights can be measured only once for
+    /// each width.
+    pub fn measurement_cache(
+        mut self,
+        cache: MeasurementCache,
+        key: impl Hash,
+    ) -> Self {
+        let key = {
+            let mut hasher = FxHasher::default();
+            key.hash(&mut hasher);
+
+            hasher.finish()
+        };
+
+        self.measurement_cache = Some((cache, key));
+        self
+    }
+
+    fn measure(&self, renderer: &Renderer, width: f32) -> f32 {
+        let bounds = Size::new(width, f32::INFINITY);
+        let view_size = self.content.borrow().view_size(bounds);
+
+        let Some(element) = (self.view)(view_size) else {
+            return 0.0;
+        };
+
+        let mut tree = Tree::new(&element);
+
+        element
+            .as_widget()
+            .layout(
+                &mut tree,
+                renderer,
+                &layout::Limits::new(Size::ZERO, bounds),
+            )
+            .size()
+            .height
+    }
+
+    /// Returns the cursor the contents should see; which is unavailable when
+    /// not interactive, or when clipping and outside of the given `bounds`.
+    fn clip_cursor(
+        &self,
+        cursor: mouse::Cursor,
+        bounds: Rectangle,
+    ) -> mouse::Cursor {
+        if !self.interactive || (self.clip && !cursor.is_over(bounds)) {
+            mouse::Cursor::Unavailable
+        } else {
+            cursor
+        }
+    }
+
+    /// Returns the [`Size`] the contents should be built for when the
+    /// [`Responsive`] has the given `bounds`.
+    ///
+    /// While a rebuild is deferred, files are dragged over the window, or
+    /// the user is interacting with the contents, they keep the [`Size`] they
+    /// were last built for; even if they are recreated by a new view.
+    fn build_size(&self, state: &State, bounds: Size) -> Size {
+        let is_frozen = (self.stable_during_drag && state.is_dragging)
+            || (self.stable_during_interaction && state.is_interacting);
+
+        match state.built_size.get() {
+            Some(size) if state.is_rebuild_deferred.get() || is_frozen => size,
+            _ => bounds,
         }
     }
 }
 
 struct Content<'a, Message, Theme, Renderer> {
     size: Size,
+    min: Size,
     layout: Option<layout::Node>,
     element: Element<'a, Message, Theme, Renderer>,
+    is_stale: Option<Box<dyn Fn(Size, Size) -> bool + 'a>>,
+    scrollbar_reservation: f32,
+    max_depth: Option<usize>,
+    is_empty: bool,
+    retained: Vec<widget::Id>,
+    debug_name: Option<&'static str>,
+    preserved_scroll: Option<widget::Id>,
+    pending_scroll: Option<RelativeOffset>,
+    fallback: Option<
+        Box<
+            dyn Fn(FallbackReason) -> Element<'a, Message, Theme, Renderer>
+                + 'a,
+        >,
+    >,
 }
 
 impl<'a, Message, Theme, Renderer> Content<'a, Message, Theme, Renderer>
@@ -69,37 +607,151 @@ where
 {
     fn layout(&mut self, tree: &mut Tree, renderer: &Renderer) {
         if self.layout.is_none() {
-            self.layout = Some(self.element.as_widget().layout(
-                tree,
-                renderer,
-                &layout::Limits::new(Size::ZERO, self.size),
-            ));
+            self.layout = Some(if self.is_empty {
+                layout::Node::default()
+            } else {
+                self.element.as_widget().layout(
+                    tree,
+                    renderer,
+                    &layout::Limits::new(self.min, self.size),
+                )
+            });
+
+            if let (Some(id), Some(offset), Some(node)) = (
+                &self.preserved_scroll,
+                self.pending_scroll.take(),
+                &self.layout,
+            ) {
+                self.element.as_widget().operate(
+                    tree,
+                    Layout::new(node),
+                    renderer,
+                    &mut scrollable::snap_to(id.clone(), offset),
+                );
+            }
         }
     }
 
+    /// Returns the [`Size`] provided to the view closure for the given
+    /// `size` of the [`Responsive`].
+    fn view_size(&self, size: Size) -> Size {
+        Size::new(
+            (size.width - self.scrollbar_reservation).max(0.0),
+            size.height,
+        )
+    }
+
     fn update(
         &mut self,
         tree: &mut Tree,
+        renderer: &Renderer,
         new_size: Size,
-        view: &dyn Fn(Size) -> Element<'a, Message, Theme, Renderer>,
+        view: &dyn Fn(Size) -> Option<Element<'a, Message, Theme, Renderer>>,
     ) {
         if self.size == new_size {
             return;
         }
 
-        self.element = view(new_size);
+        if let Some(is_stale) = &self.is_stale {
+            if self.layout.is_some()
+                && !is_stale(
+                    self.view_size(self.size),
+                    self.view_size(new_size),
+                )
+            {
+                self.size = new_size;
+                self.layout = None;
+
+                return;
+            }
+        }
+
+        let depth = DEPTH.with(Cell::get);
+        let max_depth = depth_limit(self.max_depth);
+
+        log::trace!(
+            "Rebuilding {name} for {new_size:?}",
+            name = self.debug_name.unwrap_or("Responsive"),
+        );
+
+        #[cfg(feature = "diagnostics")]
+        SNAPSHOT.with(|snapshot| {
+            *snapshot.borrow_mut() = Some(Snapshot {
+                name: self.debug_name,
+                size: new_size,
+                layout: self.layout.as_ref().map(InspectedNode::from),
+            });
+        });
+
+        let element = if max_depth.is_some_and(|max| depth >= max) {
+            log::warn!(
+                "{name} nesting limit reached at depth {depth}; \
+                contents will not be built",
+                name = self.debug_name.unwrap_or("Responsive"),
+            );
+
+            self.fallback
+                .as_ref()
+                .map(|fallback| fallback(FallbackReason::DepthLimit))
+        } else if self.fallback.is_some()
+            && (new_size.width == 0.0 || new_size.height == 0.0)
+        {
+            self.fallback
+                .as_ref()
+                .map(|fallback| fallback(FallbackReason::ZeroSize))
+        } else {
+            view(self.view_size(new_size))
+        };
+
+        if let (Some(id), Some(node)) = (&self.preserved_scroll, &self.layout) {
+            let mut offset = ScrollOffset {
+                target: id.clone(),
+                offset: None,
+            };
+
+            self.element.as_widget().operate(
+                tree,
+                Layout::new(node),
+                renderer,
+                &mut offset,
+            );
+
+            self.pending_scroll = offset.offset;
+        }
+
+        self.is_empty = element.is_none();
+        self.element = element
+            .unwrap_or_else(|| Element::new(horizontal_space().width(0)));
+
         self.size = new_size;
         self.layout = None;
 
-        tree.diff(&self.element);
+        if self.retained.is_empty() {
+            tree.diff(&self.element);
+        } else {
+            let mut stash = FxHashMap::default();
+            stash_retained(tree, &self.retained, &mut stash);
+
+            RETAINED.with(|retained| *retained.borrow_mut() = stash);
+            tree.diff(&self.element);
+            RETAINED.with(|retained| retained.borrow_mut().clear());
+        }
     }
 
+    /// Runs `f` with the contents built and laid out for the given `size`
+    /// and placed at the position of the given [`Layout`].
+    ///
+    /// The contents are only rebuilt when the size changes and only laid out
+    /// again when invalidated; so repeated calls in the same frame—like the
+    /// several passes of a chained [`Operation`]—reuse the same element and
+    /// layout.
     fn resolve<R, T>(
         &mut self,
         tree: &mut Tree,
         renderer: R,
         layout: Layout<'_>,
-        view: &dyn Fn(Size) -> Element<'a, Message, Theme, Renderer>,
+        size: Size,
+        view: &dyn Fn(Size) -> Option<Element<'a, Message, Theme, Renderer>>,
         f: impl FnOnce(
             &mut Tree,
             R,
@@ -110,7 +762,7 @@ where
     where
         R: Deref<Target = Renderer>,
     {
-        self.update(tree, layout.bounds().size(), view);
+        self.update(tree, renderer.deref(), size, view);
         self.layout(tree, renderer.deref());
 
         let content_layout = Layout::with_offset(
@@ -118,12 +770,153 @@ where
             self.layout.as_ref().unwrap(),
         );
 
+        let _nesting = Nesting::enter(self.max_depth);
+
         f(tree, renderer, content_layout, &mut self.element)
     }
 }
 
+thread_local! {
+    /// The amount of [`Responsive`] widgets currently being resolved.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    /// The smallest nesting limit of the [`Responsive`] widgets currently
+    /// being resolved.
+    static LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Returns the smallest of the given `max_depth` and the [`LIMIT`] inherited
+/// from the enclosing [`Responsive`] widgets.
+fn depth_limit(max_depth: Option<usize>) -> Option<usize> {
+    match (LIMIT.with(Cell::get), max_depth) {
+        (Some(inherited), Some(max_depth)) => Some(inherited.min(max_depth)),
+        (inherited, max_depth) => inherited.or(max_depth),
+    }
+}
+
+/// An [`Operation`] that reads the relative offset of the scrollable with
+/// the given `target` identifier.
+struct ScrollOffset {
+    target: widget::Id,
+    offset: Option<RelativeOffset>,
+}
+
+impl Operation for ScrollOffset {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        content_bounds: Rectangle,
+        translation: Vector,
+    ) {
+        if Some(&self.target) != id {
+            return;
+        }
+
+        let relative = |offset: f32, max: f32| {
+            if max > 0.0 {
+                (offset / max).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        self.offset = Some(RelativeOffset {
+            x: relative(translation.x, content_bounds.width - bounds.width),
+            y: relative(translation.y, content_bounds.height - bounds.height),
+        });
+    }
+}
+
+thread_local! {
+    /// The state of the [`Retained`] children taken out of the previous
+    /// contents of the [`Responsive`] being rebuilt.
+    static RETAINED: RefCell<FxHashMap<widget::Id, Tree>> =
+        RefCell::new(FxHashMap::default());
+}
+
+/// Takes the state of every [`Retained`] child in the given [`Tree`] with one
+/// of the given `ids` and moves it to the `stash`.
+fn stash_retained(
+    tree: &mut Tree,
+    ids: &[widget::Id],
+    stash: &mut FxHashMap<widget::Id, Tree>,
+) {
+    if tree.tag == tree::Tag::of::<RetainedState>() {
+        let RetainedState(id) = tree.state.downcast_ref::<RetainedState>();
+
+        if ids.contains(id) {
+            if let Some(child) = tree.children.first_mut() {
+                let _ = stash.insert(
+                    id.clone(),
+                    std::mem::replace(child, Tree::empty()),
+                );
+            }
+
+            return;
+        }
+    }
+
+    for child in &mut tree.children {
+        stash_retained(child, ids, stash);
+    }
+}
+
+/// Takes the stashed state of the [`Retained`] child with the given `id`,
+/// if any.
+fn take_retained(id: &widget::Id) -> Option<Tree> {
+    RETAINED.with(|retained| retained.borrow_mut().remove(id))
+}
+
+/// A guard that keeps track of the [`DEPTH`] and the [`LIMIT`] of nested
+/// [`Responsive`] widgets while they are being resolved.
+struct Nesting {
+    enclosing_limit: Option<usize>,
+}
+
+impl Nesting {
+    fn enter(max_depth: Option<usize>) -> Self {
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+
+        let limit = depth_limit(max_depth);
+
+        Self {
+            enclosing_limit: LIMIT.with(|enclosing| enclosing.replace(limit)),
+        }
+    }
+}
+
+impl Drop for Nesting {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get() - 1));
+        LIMIT.with(|limit| limit.set(self.enclosing_limit));
+    }
+}
+
 struct State {
     tree: RefCell<Tree>,
+    is_overlay_dismissed: Cell<bool>,
+    is_first_build_published: bool,
+    is_rebuild_deferred: Cell<bool>,
+    built_size: Cell<Option<Size>>,
+    clipboard: RefCell<IsolatedClipboard>,
+    is_dragging: bool,
+    is_interacting: bool,
+    is_overlay_open: Cell<bool>,
+    was_overlay_open: bool,
+    content_size: Option<Size>,
+    overflow: Size,
+    published_size: Option<Size>,
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -138,6 +931,18 @@ where
     fn state(&self) -> tree::State {
         tree::State::new(State {
             tree: RefCell::new(Tree::empty()),
+            is_overlay_dismissed: Cell::new(false),
+            is_first_build_published: false,
+            is_rebuild_deferred: Cell::new(false),
+            built_size: Cell::new(None),
+            clipboard: RefCell::new(IsolatedClipboard::default()),
+            is_dragging: false,
+            is_interacting: false,
+            is_overlay_open: Cell::new(false),
+            was_overlay_open: false,
+            content_size: None,
+            overflow: Size::ZERO,
+            published_size: None,
         })
     }
 
@@ -154,7 +959,17 @@ where
         _renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        layout::Node::new(limits.max())
+        if self.explicit_limits {
+            let mut content = self.content.borrow_mut();
+            let min = limits.min();
+
+            if content.min != min {
+                content.min = min;
+                content.layout = None;
+            }
+        }
+
+        layout::Node::new(limits.max().max(self.reserve))
     }
 
     fn operate(
@@ -162,22 +977,83 @@ where
         tree: &mut Tree,
         layout: Layout<'_>,
         renderer: &Renderer,
-        operation: &mut dyn widget::Operation,
+        operation: &mut dyn Operation,
     ) {
         let state = tree.state.downcast_mut::<State>();
         let mut content = self.content.borrow_mut();
 
-        content.resolve(
-            &mut state.tree.borrow_mut(),
-            renderer,
-            layout,
-            &self.view,
-            |tree, renderer, layout, element| {
-                element
-                    .as_widget()
-                    .operate(tree, layout, renderer, operation);
-            },
-        );
+        let id = self.id.as_ref().map(|id| &id.0);
+        let mut measurement = Measurement::default();
+
+        operation.custom(&mut measurement, id);
+
+        if let Some(width) = measurement.width {
+            measurement.height = Some(self.height_for_width(renderer, width));
+
+            operation.custom(&mut measurement, id);
+        }
+
+        let size = self.build_size(state, layout.bounds().size());
+
+        operation.container(id, layout.bounds(), &mut |operation| {
+            content.resolve(
+                &mut state.tree.borrow_mut(),
+                renderer,
+                layout,
+                size,
+                &self.view,
+                |tree, renderer, layout, element| {
+                    element
+                        .as_widget()
+                        .operate(tree, layout, renderer, operation);
+                },
+            );
+        });
+
+        state.built_size.set(Some(content.size));
+
+        let mut inspector = Inspector::default();
+
+        operation.custom(&mut inspector, id);
+
+        if inspector.is_requested && content.layout.is_some() {
+            let mut collector = WidgetCollector::default();
+
+            content.resolve(
+                &mut state.tree.borrow_mut(),
+                renderer,
+                layout,
+                size,
+                &self.view,
+                |tree, renderer, layout, element| {
+                    element.as_widget().operate(
+                        tree,
+                        layout,
+                        renderer,
+                        &mut collector,
+                    );
+                },
+            );
+
+            if let Some(node) = &content.layout {
+                inspector.inspection = Some(Inspection {
+                    name: content.debug_name,
+                    width: content.size.width,
+                    height: content.size.height,
+                    layout: InspectedNode::from(node),
+                    widgets: collector.widgets,
+                });
+
+                operation.custom(&mut inspector, id);
+            }
+        }
+
+        let mut content_bounds =
+            ContentBounds(content.layout.as_ref().map(|node| {
+                node.bounds() + (layout.position() - Point::ORIGIN)
+            }));
+
+        operation.custom(&mut content_bounds, id);
     }
 
     fn on_event(
@@ -194,13 +1070,104 @@ where
         let state = tree.state.downcast_mut::<State>();
         let mut content = self.content.borrow_mut();
 
+        if let Some(id) = &self.id {
+            let size = layout.bounds().size();
+
+            if state.published_size != Some(size) {
+                state.published_size = Some(size);
+                publish_bounds(id, size);
+            }
+        }
+
+        if !self.interactive
+            && !matches!(
+                event,
+                Event::Window(window::Event::RedrawRequested(_))
+            )
+        {
+            return event::Status::Ignored;
+        }
+
         let mut local_messages = vec![];
         let mut local_shell = Shell::new(&mut local_messages);
 
+        let mut isolated_clipboard = self
+            .isolated_clipboard
+            .then(|| state.clipboard.borrow_mut());
+
+        let clipboard: &mut dyn Clipboard = match &mut isolated_clipboard {
+            Some(isolated_clipboard) => &mut **isolated_clipboard,
+            None => clipboard,
+        };
+
+        let cursor = self.clip_cursor(cursor, layout.bounds());
+
+        if self.stable_during_drag
+            && matches!(event, Event::Window(window::Event::FileHovered(_)))
+        {
+            state.is_dragging = true;
+        }
+
+        let is_drag_over = matches!(
+            event,
+            Event::Window(
+                window::Event::FileDropped(_) | window::Event::FilesHoveredLeft
+            )
+        );
+
+        let is_press = matches!(
+            event,
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+                | Event::Touch(touch::Event::FingerPressed { .. })
+        );
+
+        // A release may never arrive if the cursor leaves the window or
+        // the window loses focus in the middle of an interaction
+        let is_interaction_over = matches!(
+            event,
+            Event::Mouse(
+                mouse::Event::ButtonReleased(_) | mouse::Event::CursorLeft
+            ) | Event::Touch(
+                touch::Event::FingerLifted { .. }
+                    | touch::Event::FingerLost { .. }
+            ) | Event::Window(window::Event::Unfocused)
+        );
+
+        let redraw_requested_at = match &event {
+            Event::Window(window::Event::RedrawRequested(now)) => Some(*now),
+            _ => None,
+        };
+
+        if let (Some(now), Some((cache, _))) =
+            (redraw_requested_at, &self.measurement_cache)
+        {
+            cache.sweep(now);
+        }
+
+        if let Some(now) = redraw_requested_at {
+            let is_rebuild_deferred = !state.is_rebuild_deferred.get()
+                && state
+                    .built_size
+                    .get()
+                    .is_some_and(|size| size != layout.bounds().size())
+                && self
+                    .frame_budget
+                    .is_some_and(|budget| now.elapsed() > budget);
+
+            state.is_rebuild_deferred.set(is_rebuild_deferred);
+
+            if is_rebuild_deferred {
+                shell.request_redraw(window::RedrawRequest::NextFrame);
+            }
+        }
+
+        let size = self.build_size(state, layout.bounds().size());
+
         let status = content.resolve(
             &mut state.tree.borrow_mut(),
             renderer,
             layout,
+            size,
             &self.view,
             |tree, renderer, layout, element| {
                 element.as_widget_mut().on_event(
@@ -216,12 +1183,89 @@ where
             },
         );
 
+        if is_drag_over {
+            state.is_dragging = false;
+        }
+
+        if self.stable_during_interaction {
+            if is_press && status == event::Status::Captured {
+                state.is_interacting = true;
+            } else if is_interaction_over {
+                state.is_interacting = false;
+            }
+        }
+
+        // An overlay dismissed with Escape stays hidden until the next press,
+        // which closes it the same way a click outside of it would.
+        if is_press {
+            state.is_overlay_dismissed.set(false);
+        }
+
+        state.built_size.set(Some(content.size));
+
         if local_shell.is_layout_invalid() {
             content.layout = None;
         }
 
         shell.merge(local_shell, std::convert::identity);
 
+        if let Some(on_first_build) = &self.on_first_build {
+            if !state.is_first_build_published
+                && content.size.width > 0.0
+                && content.size.height > 0.0
+            {
+                shell.publish(on_first_build(content.size));
+                state.is_first_build_published = true;
+            }
+        }
+
+        if let Some(on_overlay_change) = &self.on_overlay_change {
+            let is_overlay_open = state.is_overlay_open.get();
+
+            if is_overlay_open != state.was_overlay_open {
+                shell.publish(on_overlay_change(is_overlay_open));
+                state.was_overlay_open = is_overlay_open;
+            }
+        }
+
+        if let (Some(on_overflow), Some(node)) =
+            (&self.on_overflow, &content.layout)
+        {
+            let bounds = layout.bounds().size();
+            let content_size = extent(node);
+
+            let overflow = |content: f32, available: f32| {
+                let overflow = content - available;
+
+                if overflow >= 1.0 {
+                    overflow
+                } else {
+                    0.0
+                }
+            };
+
+            let overflow = Size::new(
+                overflow(content_size.width, bounds.width),
+                overflow(content_size.height, bounds.height),
+            );
+
+            if overflow != state.overflow {
+                shell.publish(on_overflow(overflow));
+                state.overflow = overflow;
+            }
+        }
+
+        if let (Some(on_content_resize), Some(node)) =
+            (&self.on_content_resize, &content.layout)
+        {
+            let content_size = node.size();
+
+            if state.content_size != Some(content_size) {
+                shell.publish(on_content_resize(content_size));
+                state.content_size = Some(content_size);
+            }
+        }
+
         status
     }
 
@@ -237,18 +1281,118 @@ where
     ) {
         let state = tree.state.downcast_ref::<State>();
         let mut content = self.content.borrow_mut();
+        let mut tree = state.tree.borrow_mut();
+        let is_rebuild_deferred = state.is_rebuild_deferred.get();
 
-        content.resolve(
-            &mut state.tree.borrow_mut(),
-            renderer,
-            layout,
-            &self.view,
-            |tree, renderer, layout, element| {
-                element.as_widget().draw(
-                    tree, renderer, theme, style, layout, cursor, viewport,
+        let size = self.build_size(state, layout.bounds().size());
+
+        content.update(&mut tree, renderer, size, &self.view);
+        content.layout(&mut tree, renderer);
+
+        state.built_size.set(Some(content.size));
+
+        if content.is_empty {
+            return;
+        }
+
+        let cursor = self.clip_cursor(cursor, layout.bounds());
+
+        let mut draw_contents =
+            |renderer: &mut Renderer, viewport: &Rectangle| {
+                if is_rebuild_deferred && content.size != layout.bounds().size()
+                {
+                    let stale_size = content.size;
+
+                    let mut draw_stale =
+                        |renderer: &mut Renderer, cursor: mouse::Cursor| {
+                            content.resolve(
+                                &mut tree,
+                                renderer,
+                                layout,
+                                size,
+                                &self.view,
+                                |tree, renderer, layout, element| {
+                                    element.as_widget().draw(
+                                        tree, renderer, theme, style, layout,
+                                        cursor, viewport,
+                                    );
+                                },
+                            );
+                        };
+
+                    match self.stretch_mode {
+                        StretchMode::Keep => {
+                            draw_stale(renderer, cursor);
+                        }
+                        StretchMode::Clip => {
+                            if let Some(clipped_viewport) =
+                                layout.bounds().intersection(viewport)
+                            {
+                                renderer.with_layer(
+                                    clipped_viewport,
+                                    |renderer| {
+                                        draw_stale(renderer, cursor);
+                                    },
+                                );
+                            }
+                        }
+                        StretchMode::Scale => {
+                            let bounds = layout.bounds().size();
+
+                            let scale = (bounds.width / stale_size.width)
+                                .min(bounds.height / stale_size.height);
+
+                            if scale.is_finite() && scale > 0.0 {
+                                let position = layout.position();
+
+                                renderer.with_transformation(
+                                    Transformation::translate(
+                                        position.x, position.y,
+                                    ) * Transformation::scale(scale)
+                                        * Transformation::translate(
+                                            -position.x,
+                                            -position.y,
+                                        ),
+                                    |renderer| {
+                                        draw_stale(
+                                            renderer,
+                                            mouse::Cursor::Unavailable,
+                                        );
+                                    },
+                                );
+                            }
+                        }
+                    }
+
+                    return;
+                }
+
+                content.resolve(
+                    &mut tree,
+                    renderer,
+                    layout,
+                    size,
+                    &self.view,
+                    |tree, renderer, layout, element| {
+                        element.as_widget().draw(
+                            tree, renderer, theme, style, layout, cursor,
+                            viewport,
+                        );
+                    },
                 );
-            },
-        );
+            };
+
+        if self.clip {
+            if let Some(clipped_viewport) =
+                layout.bounds().intersection(viewport)
+            {
+                renderer.with_layer(clipped_viewport, |renderer| {
+                    draw_contents(renderer, &clipped_viewport);
+                });
+            }
+        } else {
+            draw_contents(renderer, viewport);
+        }
     }
 
     fn mouse_interaction(
@@ -259,20 +1403,31 @@ where
         viewport: &Rectangle,
         renderer: &Renderer,
     ) -> mouse::Interaction {
+        if !self.interactive {
+            return mouse::Interaction::default();
+        }
+
         let state = tree.state.downcast_ref::<State>();
         let mut content = self.content.borrow_mut();
+        let cursor = self.clip_cursor(cursor, layout.bounds());
+        let size = self.build_size(state, layout.bounds().size());
 
-        content.resolve(
+        let interaction = content.resolve(
             &mut state.tree.borrow_mut(),
             renderer,
             layout,
+            size,
             &self.view,
             |tree, renderer, layout, element| {
                 element
                     .as_widget()
                     .mouse_interaction(tree, layout, cursor, viewport, renderer)
             },
-        )
+        );
+
+        state.built_size.set(Some(content.size));
+
+        interaction
     }
 
     fn overlay<'b>(
@@ -284,23 +1439,51 @@ where
     ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
         use std::ops::DerefMut;
 
+        if !self.interactive {
+            return None;
+        }
+
         let state = tree.state.downcast_ref::<State>();
 
+        if state.is_overlay_dismissed.get() {
+            state.is_overlay_open.set(false);
+
+            return None;
+        }
+
+        let translation =
+            translation + self.overlay_anchor.offset(layout.bounds().size());
+
+        // The overlay is built before any event of a frame is handled; so,
+        // with a frame budget, it never rebuilds the contents for a new size
+        // and leaves the decision of deferring the rebuild to `on_event`.
+        let size = match state.built_size.get() {
+            Some(size) if self.frame_budget.is_some() => size,
+            _ => self.build_size(state, layout.bounds().size()),
+        };
+
         let overlay = OverlayBuilder {
             content: self.content.borrow_mut(),
             tree: state.tree.borrow_mut(),
+            is_dismissed: self
+                .close_overlay_on_escape
+                .then_some(&state.is_overlay_dismissed),
+            clipboard: self.isolated_clipboard.then_some(&state.clipboard),
             types: PhantomData,
             overlay_builder: |content: &mut RefMut<
                 '_,
                 Content<'_, _, _, _>,
             >,
                               tree| {
-                content.update(tree, layout.bounds().size(), &self.view);
+                content.update(tree, renderer, size, &self.view);
                 content.layout(tree, renderer);
 
+                state.built_size.set(Some(content.size));
+
                 let Content {
                     element,
                     layout: content_layout_node,
+                    max_depth,
                     ..
                 } = content.deref_mut();
 
@@ -309,6 +1492,8 @@ where
                     content_layout_node.as_ref().unwrap(),
                 );
 
+                let _nesting = Nesting::enter(*max_depth);
+
                 (
                     element
                         .as_widget_mut()
@@ -320,6 +1505,10 @@ where
         }
         .build();
 
+        state
+            .is_overlay_open
+            .set(overlay.with_overlay(|(overlay, _layout)| overlay.is_some()));
+
         Some(overlay::Element::new(Box::new(overlay)))
     }
 }
@@ -337,10 +1526,252 @@ where
     }
 }
 
+/// How the stale contents of a [`Responsive`] are drawn while their rebuild
+/// is deferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StretchMode {
+    /// The stale contents are drawn as they are, even if they overflow the
+    /// new bounds.
+    #[default]
+    Keep,
+
+    /// The stale contents are drawn as they are, but clipped to the new
+    /// bounds.
+    Clip,
+
+    /// The stale contents are scaled uniformly to fit the new bounds.
+    Scale,
+}
+
+/// A cache of natural heights that can be shared by many [`Responsive`]
+/// widgets.
+///
+/// Cloning a [`MeasurementCache`] produces a handle to the same cache. It is
+/// neither [`Send`] nor [`Sync`], so it can only be shared by widgets living in
+/// the same thread.
+///
+/// Since the contents are identified by a key, the cache must be cleared when
+/// the state they are built from changes.
+///
+/// The cache holds at most [`MeasurementCache::CAPACITY`] measurements. Once
+/// full, the oldest measurement is evicted to make room for a new one.
+#[derive(Debug, Clone, Default)]
+pub struct MeasurementCache {
+    heights: Rc<RefCell<FxHashMap<(u64, u32), (f32, Instant)>>>,
+    ttl: Option<Duration>,
+}
+
+impl MeasurementCache {
+    /// The maximum amount of measurements kept by a [`MeasurementCache`].
+    pub const CAPACITY: usize = 1024;
+
+    /// Creates a new empty [`MeasurementCache`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty [`MeasurementCache`] whose measurements expire
+    /// after the given time-to-live.
+    ///
+    /// Otherwise, measurements are kept until the cache is cleared or they
+    /// are evicted to make room for new ones. Expired measurements are
+    /// evicted when looked up and every time a [`Responsive`] using the
+    /// cache is redrawn.
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..Self::default()
+        }
+    }
+
+    /// Removes all the measurements from the [`MeasurementCache`].
+    pub fn clear(&self) {
+        self.heights.borrow_mut().clear();
+    }
+
+    fn is_expired(&self, measured_at: Instant, now: Instant) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.saturating_duration_since(measured_at) > ttl)
+    }
+
+    fn get(&self, key: (u64, u32), now: Instant) -> Option<f32> {
+        let mut heights = self.heights.borrow_mut();
+        let (height, measured_at) = *heights.get(&key)?;
+
+        if self.is_expired(measured_at, now) {
+            let _ = heights.remove(&key);
+
+            return None;
+        }
+
+        Some(height)
+    }
+
+    fn insert(&self, key: (u64, u32), height: f32, now: Instant) {
+        let mut heights = self.heights.borrow_mut();
+
+        if heights.len() >= Self::CAPACITY && !heights.contains_key(&key) {
+            let oldest = heights
+                .iter()
+                .min_by_key(|(_, (_, measured_at))| *measured_at)
+                .map(|(key, _)| *key);
+
+            if let Some(oldest) = oldest {
+                let _ = heights.remove(&oldest);
+            }
+        }
+
+        let _ = heights.insert(key, (height, now));
+    }
+
+    fn sweep(&self, now: Instant) {
+        if self.ttl.is_some() {
+            self.heights.borrow_mut().retain(|_, (_, measured_at)| {
+                !self.is_expired(*measured_at, now)
+            });
+        }
+    }
+}
+
+/// The anchor of the overlays opened by the contents of a [`Responsive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Anchor {
+    /// The top-left corner; where the contents place their overlays.
+    #[default]
+    TopLeft,
+
+    /// The top-right corner.
+    TopRight,
+
+    /// The bottom-left corner.
+    BottomLeft,
+
+    /// The bottom-right corner.
+    BottomRight,
+}
+
+/// The reason why the contents of a [`Responsive`] could not be built by
+/// its `view` closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackReason {
+    /// The [`Responsive`] has no width or no height.
+    ZeroSize,
+
+    /// The nesting limit set with [`Responsive::limit_depth`] was reached.
+    DepthLimit,
+}
+
+impl Anchor {
+    fn offset(self, size: Size) -> Vector {
+        match self {
+            Anchor::TopLeft => Vector::ZERO,
+            Anchor::TopRight => Vector::new(size.width, 0.0),
+            Anchor::BottomLeft => Vector::new(0.0, size.height),
+            Anchor::BottomRight => Vector::new(size.width, size.height),
+        }
+    }
+}
+
+/// Creates a [`Responsive`] widget that only rebuilds its contents when its
+/// [`Size`] or the given `dependency` change.
+///
+/// The [`Responsive`] is kept alive by a [`Lazy`] widget for as long as the
+/// hash of the `dependency` stays the same; therefore, the `view` closure
+/// will only be called again when the [`Responsive`] is resized.
+///
+/// Only the hash of the `dependency` is compared. For large data, a cheap
+/// revision number—like a `u64` bumped on every change—can be used as the
+/// `dependency` instead; in which case, keeping it up to date is up to the
+/// caller.
+pub fn lazy<'a, Message, Theme, Renderer, Dependency>(
+    dependency: Dependency,
+    view: impl Fn(Size, &Dependency) -> Element<'static, Message, Theme, Renderer>
+        + 'static,
+) -> Lazy<
+    'a,
+    Message,
+    Theme,
+    Renderer,
+    Dependency,
+    Responsive<'static, Message, Theme, Renderer>,
+>
+where
+    Message: 'static,
+    Theme: 'static,
+    Renderer: core::Renderer + 'static,
+    Dependency: Hash + Clone + 'static,
+{
+    let view = Rc::new(view);
+
+    Lazy::new(dependency, move |dependency| {
+        let view = view.clone();
+        let dependency = dependency.clone();
+
+        Responsive::new(move |size| view(size, &dependency))
+    })
+}
+
+/// Creates a [`Responsive`] widget that builds its contents from some
+/// shared `state` and only rebuilds them when its [`Size`] or the given
+/// `revision` change.
+///
+/// Instead of cloning large application state into the `view` closure on
+/// every render, the state can be kept behind an [`Rc`] and borrowed by the
+/// closure; only the cheap `revision` is hashed to detect changes. Keeping
+/// the `revision` in sync with the `state` is up to the caller.
+///
+/// ```no_run
+/// # mod iced { pub mod widget { pub use iced_widget::*; } }
+/// # pub type Element<'a, Message> = iced_widget::core::Element<'a, Message, iced_widget::Theme, iced_widget::Renderer>;
+/// use iced::widget::{responsive, text};
+/// use std::rc::Rc;
+///
+/// struct State {
+///     items: Rc<Vec<String>>,
+///     revision: u64,
+/// }
+///
+/// #[derive(Debug, Clone)]
+/// enum Message {
+///     // ...
+/// }
+///
+/// fn view(state: &State) -> Element<'_, Message> {
+///     responsive::shared(&state.items, state.revision, |size, items| {
+///         text(format!("{} items in {}px", items.len(), size.width)).into()
+///     })
+///     .into()
+/// }
+/// ```
+pub fn shared<'a, Message, Theme, Renderer, T>(
+    state: &Rc<T>,
+    revision: u64,
+    view: impl Fn(Size, &T) -> Element<'static, Message, Theme, Renderer> + 'static,
+) -> Lazy<
+    'a,
+    Message,
+    Theme,
+    Renderer,
+    u64,
+    Responsive<'static, Message, Theme, Renderer>,
+>
+where
+    Message: 'static,
+    Theme: 'static,
+    Renderer: core::Renderer + 'static,
+    T: 'static,
+{
+    let state = Rc::clone(state);
+
+    lazy(revision, move |size, _revision| view(size, &state))
+}
+
 #[self_referencing]
 struct Overlay<'a, 'b, Message, Theme, Renderer> {
     content: RefMut<'a, Content<'b, Message, Theme, Renderer>>,
     tree: RefMut<'a, Tree>,
+    is_dismissed: Option<&'a Cell<bool>>,
+    clipboard: Option<&'a RefCell<IsolatedClipboard>>,
     types: PhantomData<Message>,
 
     #[borrows(mut content, mut tree)]
@@ -420,6 +1851,16 @@ where
         shell: &mut Shell<'_, Message>,
     ) -> event::Status {
         let mut is_layout_invalid = false;
+        let is_escape = is_escape_pressed(&event);
+
+        let isolated_clipboard = *self.borrow_clipboard();
+        let mut isolated_clipboard =
+            isolated_clipboard.map(RefCell::borrow_mut);
+
+        let clipboard: &mut dyn Clipboard = match &mut isolated_clipboard {
+            Some(isolated_clipboard) => &mut **isolated_clipboard,
+            None => clipboard,
+        };
 
         let event_status = self
             .with_overlay_mut_maybe(|overlay| {
@@ -439,6 +1880,18 @@ where
             });
         }
 
+        if is_escape
+            && event_status == event::Status::Ignored
+            && self.with_overlay(|(overlay, _layout)| overlay.is_some())
+        {
+            if let Some(is_dismissed) = self.borrow_is_dismissed() {
+                is_dismissed.set(true);
+                shell.invalidate_layout();
+
+                return event::Status::Captured;
+            }
+        }
+
         event_status
     }
 
@@ -454,3 +1907,1246 @@ where
         .unwrap_or_default()
     }
 }
+
+/// An in-memory [`Clipboard`] used by the contents of a [`Responsive`] that
+/// are isolated from the system clipboard.
+#[derive(Debug, Default)]
+struct IsolatedClipboard {
+    standard: Option<String>,
+    primary: Option<String>,
+}
+
+impl Clipboard for IsolatedClipboard {
+    fn read(&self, kind: clipboard::Kind) -> Option<String> {
+        match kind {
+            clipboard::Kind::Standard => self.standard.clone(),
+            clipboard::Kind::Primary => self.primary.clone(),
+        }
+    }
+
+    fn write(&mut self, kind: clipboard::Kind, contents: String) {
+        match kind {
+            clipboard::Kind::Standard => self.standard = Some(contents),
+            clipboard::Kind::Primary => self.primary = Some(contents),
+        }
+    }
+}
+
+/// Returns the [`Size`] spanned by the given [`layout::Node`] and all of its
+/// descendants; which may exceed the size of the node itself if children
+/// do not fit in it.
+fn extent(node: &layout::Node) -> Size {
+    node.children().iter().fold(node.size(), |span, child| {
+        let position = child.bounds().position();
+        let child = extent(child);
+
+        Size::new(
+            span.width.max(position.x + child.width),
+            span.height.max(position.y + child.height),
+        )
+    })
+}
+
+fn is_escape_pressed(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Escape),
+            ..
+        })
+    )
+}
+
+/// The last published size and the subscribers of [`bounds_changes`] of a
+/// [`Responsive`].
+#[derive(Default)]
+struct Bounds {
+    size: Option<Size>,
+    subscribers: Vec<mpsc::UnboundedSender<Size>>,
+}
+
+/// The [`Bounds`] of every [`Responsive`] with an [`Id`].
+static BOUNDS: once_cell::sync::Lazy<Mutex<FxHashMap<Id, Bounds>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(FxHashMap::default()));
+
+fn publish_bounds(id: &Id, size: Size) {
+    let mut bounds = BOUNDS.lock().expect("Lock bounds");
+    let bounds = bounds.entry(id.clone()).or_default();
+
+    bounds.size = Some(size);
+    bounds
+        .subscribers
+        .retain(|sender| sender.unbounded_send(size).is_ok());
+}
+
+/// Returns a [`Subscription`] that produces the new size of the
+/// [`Responsive`] widget with the given [`Id`] every time it changes.
+///
+/// The size is published the first time the widget processes an event
+/// with new bounds, so the application can react to layout changes without
+/// threading them through the view. A new subscription starts with the last
+/// size published, if any; so it does not miss the widget being laid out
+/// before it was subscribed.
+pub fn bounds_changes(id: Id) -> Subscription<Size> {
+    let subscriber = id.clone();
+
+    let receiver = stream::once(async move {
+        let (sender, receiver) = mpsc::unbounded();
+
+        let mut bounds = BOUNDS.lock().expect("Lock bounds");
+        let bounds = bounds.entry(subscriber).or_default();
+
+        bounds.subscribers.retain(|sender| !sender.is_closed());
+
+        if let Some(size) = bounds.size {
+            let _ = sender.unbounded_send(size);
+        }
+
+        bounds.subscribers.push(sender);
+
+        receiver
+    })
+    .flatten();
+
+    Subscription::run_with_id(id, receiver)
+}
+
+/// The identifier of a [`Responsive`] widget.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Id(widget::Id);
+
+impl Id {
+    /// Creates a custom [`Id`].
+    pub fn new(id: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self(widget::Id::new(id))
+    }
+
+    /// Creates a unique [`Id`].
+    ///
+    /// This function produces a different [`Id`] every time it is called.
+    pub fn unique() -> Self {
+        Self(widget::Id::unique())
+    }
+}
+
+impl From<Id> for widget::Id {
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+/// A measurement request exchanged between a [`Responsive`] widget and an
+/// [`Operation`].
+#[derive(Debug, Default)]
+struct Measurement {
+    width: Option<f32>,
+    height: Option<f32>,
+}
+
+/// Produces a [`Task`] that measures the natural height of the contents of
+/// the [`Responsive`] widgets with the given ids at the given `width`.
+///
+/// The heights are returned in the same order as the ids; `None` is
+/// produced for any [`Responsive`] that could not be found.
+///
+/// The contents are built and laid out with an unbounded height in a
+/// separate tree, so the live layout of the widgets is not disturbed. This
+/// is useful to give a row of tiles the height of the tallest one.
+pub fn natural_heights(ids: Vec<Id>, width: f32) -> Task<Vec<Option<f32>>> {
+    struct NaturalHeights {
+        targets: Vec<widget::Id>,
+        width: f32,
+        heights: Vec<Option<f32>>,
+    }
+
+    impl Operation<Vec<Option<f32>>> for NaturalHeights {
+        fn container(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(
+                &mut dyn Operation<Vec<Option<f32>>>,
+            ),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&widget::Id>) {
+            let Some(measurement) = state.downcast_mut::<Measurement>() else {
+                return;
+            };
+
+            let Some(index) = id.and_then(|id| {
+                self.targets.iter().position(|target| target == id)
+            }) else {
+                return;
+            };
+
+            match measurement.height {
+                Some(height) => {
+                    self.heights[index] = Some(height);
+                }
+                None => {
+                    measurement.width = Some(self.width);
+                }
+            }
+        }
+
+        fn finish(&self) -> operation::Outcome<Vec<Option<f32>>> {
+            operation::Outcome::Some(self.heights.clone())
+        }
+    }
+
+    let heights = vec![None; ids.len()];
+
+    task::widget(NaturalHeights {
+        targets: ids.into_iter().map(widget::Id::from).collect(),
+        width,
+        heights,
+    })
+}
+
+/// The state of a [`Responsive`] widget right before its contents were last
+/// rebuilt.
+#[cfg(feature = "diagnostics")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// The debug name of the [`Responsive`], if any.
+    pub name: Option<&'static str>,
+
+    /// The size the contents were being rebuilt for.
+    pub size: Size,
+
+    /// The last known good layout of the contents, if any.
+    pub layout: Option<InspectedNode>,
+}
+
+#[cfg(feature = "diagnostics")]
+thread_local! {
+    /// The [`Snapshot`] of the last [`Responsive`] rebuilt in this thread.
+    static SNAPSHOT: RefCell<Option<Snapshot>> = const { RefCell::new(None) };
+}
+
+/// Returns the [`Snapshot`] of the last [`Responsive`] whose contents were
+/// rebuilt in the current thread, if any.
+///
+/// Since panic hooks run in the panicking thread, this can be called from a
+/// hook installed with [`std::panic::set_hook`] to find out which region and
+/// size triggered a panic in a `view` closure.
+#[cfg(feature = "diagnostics")]
+pub fn last_snapshot() -> Option<Snapshot> {
+    SNAPSHOT.with(|snapshot| snapshot.borrow().clone())
+}
+
+/// The resolved layout and widgets of the contents of a [`Responsive`]
+/// widget.
+///
+/// With the `inspection-serde` feature enabled, it can be serialized; for
+/// instance, to compare layouts across changes in snapshot tests.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "inspection-serde", derive(serde::Serialize))]
+pub struct Inspection {
+    /// The debug name of the [`Responsive`], if any.
+    pub name: Option<&'static str>,
+
+    /// The width the contents were built for.
+    pub width: f32,
+
+    /// The height the contents were built for.
+    pub height: f32,
+
+    /// The layout of the contents.
+    pub layout: InspectedNode,
+
+    /// The widgets of the contents that take part in operations.
+    pub widgets: Vec<InspectedWidget>,
+}
+
+/// A node of an [`Inspection`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "inspection-serde", derive(serde::Serialize))]
+pub struct InspectedNode {
+    /// The horizontal position of the node, relative to its parent.
+    pub x: f32,
+
+    /// The vertical position of the node, relative to its parent.
+    pub y: f32,
+
+    /// The width of the node.
+    pub width: f32,
+
+    /// The height of the node.
+    pub height: f32,
+
+    /// The children of the node.
+    pub children: Vec<InspectedNode>,
+}
+
+impl From<&layout::Node> for InspectedNode {
+    fn from(node: &layout::Node) -> Self {
+        let bounds = node.bounds();
+
+        Self {
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+            children: node.children().iter().map(Self::from).collect(),
+        }
+    }
+}
+
+/// A widget of an [`Inspection`].
+///
+/// A widget that takes part in several kinds of operations, like a text
+/// input that is also focusable, appears once for each kind.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "inspection-serde", derive(serde::Serialize))]
+pub struct InspectedWidget {
+    /// The kind of the widget.
+    pub kind: WidgetKind,
+
+    /// The [`widget::Id`] of the widget, if any.
+    #[cfg_attr(
+        feature = "inspection-serde",
+        serde(serialize_with = "serialize_id")
+    )]
+    pub id: Option<widget::Id>,
+
+    /// The children of the widget, if it is a container.
+    pub children: Vec<InspectedWidget>,
+}
+
+/// The kind of an [`InspectedWidget`]; that is, the kind of operation it
+/// takes part in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "inspection-serde", derive(serde::Serialize))]
+pub enum WidgetKind {
+    /// A widget that contains other widgets.
+    Container,
+
+    /// A widget that can be focused.
+    Focusable,
+
+    /// A widget that can be scrolled.
+    Scrollable,
+
+    /// A widget that has text input.
+    TextInput,
+
+    /// A custom widget with some state.
+    Custom,
+}
+
+/// Serializes a [`widget::Id`] with its [`Debug`] representation, since it
+/// has no other.
+#[cfg(feature = "inspection-serde")]
+fn serialize_id<S>(
+    id: &Option<widget::Id>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match id {
+        Some(id) => serializer.serialize_some(&format!("{id:?}")),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// An [`Operation`] that collects the [`InspectedWidget`]s of some contents.
+#[derive(Debug, Default)]
+struct WidgetCollector {
+    widgets: Vec<InspectedWidget>,
+}
+
+impl WidgetCollector {
+    fn push(&mut self, kind: WidgetKind, id: Option<&widget::Id>) {
+        self.widgets.push(InspectedWidget {
+            kind,
+            id: id.cloned(),
+            children: Vec::new(),
+        });
+    }
+}
+
+impl Operation for WidgetCollector {
+    fn container(
+        &mut self,
+        id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation),
+    ) {
+        let mut children = Self::default();
+
+        operate_on_children(&mut children);
+
+        self.widgets.push(InspectedWidget {
+            kind: WidgetKind::Container,
+            id: id.cloned(),
+            children: children.widgets,
+        });
+    }
+
+    fn focusable(
+        &mut self,
+        _state: &mut dyn operation::Focusable,
+        id: Option<&widget::Id>,
+    ) {
+        self.push(WidgetKind::Focusable, id);
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        _content_bounds: Rectangle,
+        _translation: Vector,
+    ) {
+        self.push(WidgetKind::Scrollable, id);
+    }
+
+    fn text_input(
+        &mut self,
+        _state: &mut dyn operation::TextInput,
+        id: Option<&widget::Id>,
+    ) {
+        self.push(WidgetKind::TextInput, id);
+    }
+
+    fn custom(&mut self, _state: &mut dyn Any, id: Option<&widget::Id>) {
+        self.push(WidgetKind::Custom, id);
+    }
+}
+
+/// An inspection request exchanged between a [`Responsive`] widget and an
+/// [`Operation`].
+#[derive(Debug, Default)]
+struct Inspector {
+    is_requested: bool,
+    inspection: Option<Inspection>,
+}
+
+/// Produces a [`Task`] that inspects the resolved layout and widgets of the
+/// contents of the [`Responsive`] with the given [`Id`].
+///
+/// `None` is produced if the [`Responsive`] could not be found or its
+/// contents have not been laid out yet.
+pub fn inspect(id: Id) -> Task<Option<Inspection>> {
+    struct Inspect {
+        target: widget::Id,
+        inspection: Option<Inspection>,
+    }
+
+    impl Operation<Option<Inspection>> for Inspect {
+        fn container(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(
+                &mut dyn Operation<Option<Inspection>>,
+            ),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&widget::Id>) {
+            let Some(inspector) = state.downcast_mut::<Inspector>() else {
+                return;
+            };
+
+            if id != Some(&self.target) {
+                return;
+            }
+
+            match inspector.inspection.take() {
+                Some(inspection) => {
+                    self.inspection = Some(inspection);
+                }
+                None => {
+                    inspector.is_requested = true;
+                }
+            }
+        }
+
+        fn finish(&self) -> operation::Outcome<Option<Inspection>> {
+            operation::Outcome::Some(self.inspection.clone())
+        }
+    }
+
+    task::widget(Inspect {
+        target: id.into(),
+        inspection: None,
+    })
+}
+
+/// The bounds of the contents of a [`Responsive`] widget, exchanged with an
+/// [`Operation`].
+#[derive(Debug)]
+struct ContentBounds(Option<Rectangle>);
+
+/// Produces a [`Task`] that returns the bounds of the laid out contents of
+/// the [`Responsive`] with the given [`Id`].
+///
+/// The bounds of the contents may differ from the bounds of the
+/// [`Responsive`] itself; for instance, when the contents do not fill all
+/// of the available space. This is useful to anchor tooltips or menus.
+///
+/// `None` is produced if the [`Responsive`] could not be found or its
+/// contents have not been laid out yet.
+pub fn content_bounds(id: Id) -> Task<Option<Rectangle>> {
+    struct FindContentBounds {
+        target: widget::Id,
+        bounds: Option<Rectangle>,
+    }
+
+    impl Operation<Option<Rectangle>> for FindContentBounds {
+        fn container(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(
+                &mut dyn Operation<Option<Rectangle>>,
+            ),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&widget::Id>) {
+            let Some(ContentBounds(bounds)) =
+                state.downcast_ref::<ContentBounds>()
+            else {
+                return;
+            };
+
+            if id == Some(&self.target) {
+                self.bounds = *bounds;
+            }
+        }
+
+        fn finish(&self) -> operation::Outcome<Option<Rectangle>> {
+            operation::Outcome::Some(self.bounds)
+        }
+    }
+
+    task::widget(FindContentBounds {
+        target: id.into(),
+        bounds: None,
+    })
+}
+
+/// Wraps the given content in a [`Retained`] widget with the given
+/// [`widget::Id`].
+pub fn retained<'a, Message, Theme, Renderer>(
+    id: impl Into<widget::Id>,
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Retained<'a, Message, Theme, Renderer> {
+    Retained {
+        id: id.into(),
+        content: content.into(),
+    }
+}
+
+/// A child of a [`Responsive`] that can keep its state across rebuilds,
+/// even if it changes position.
+///
+/// The identifier of the child must be passed to [`Responsive::retain`].
+#[allow(missing_debug_implementations)]
+pub struct Retained<'a, Message, Theme, Renderer> {
+    id: widget::Id,
+    content: Element<'a, Message, Theme, Renderer>,
+}
+
+struct RetainedState(widget::Id);
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Retained<'a, Message, Theme, Renderer>
+where
+    Renderer: core::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<RetainedState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(RetainedState(self.id.clone()))
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        let child = match take_retained(&self.id) {
+            Some(mut child) => {
+                child.diff(&self.content);
+                child
+            }
+            None => Tree::new(&self.content),
+        };
+
+        vec![child]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let RetainedState(id) = tree.state.downcast_mut::<RetainedState>();
+
+        if *id != self.id {
+            *id = self.id.clone();
+        }
+
+        if let Some(child) = take_retained(&self.id) {
+            tree.children = vec![child];
+        }
+
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content.as_widget().operate(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            operation,
+        );
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Retained<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: core::Renderer + 'a,
+{
+    fn from(retained: Retained<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(retained)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::renderer::Quad;
+    use crate::core::Background;
+
+    /// A renderer that counts the transformations it is asked to apply.
+    #[derive(Default)]
+    struct Recorder {
+        transformations: usize,
+    }
+
+    impl core::Renderer for Recorder {
+        fn start_layer(&mut self, _bounds: Rectangle) {}
+
+        fn end_layer(&mut self) {}
+
+        fn start_transformation(&mut self, _transformation: Transformation) {
+            self.transformations += 1;
+        }
+
+        fn end_transformation(&mut self) {}
+
+        fn clear(&mut self) {}
+
+        fn fill_quad(
+            &mut self,
+            _quad: Quad,
+            _background: impl Into<Background>,
+        ) {
+        }
+    }
+
+    fn draw<Message, Renderer: core::Renderer>(
+        widget: &dyn Widget<Message, (), Renderer>,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        node: &layout::Node,
+    ) {
+        widget.draw(
+            tree,
+            renderer,
+            &(),
+            &renderer::Style::default(),
+            Layout::new(node),
+            mouse::Cursor::Unavailable,
+            &node.bounds(),
+        );
+    }
+
+    fn on_event<Message, Theme, Renderer: core::Renderer>(
+        widget: &mut dyn Widget<Message, Theme, Renderer>,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        node: &layout::Node,
+        event: Event,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Vec<Message>, Option<window::RedrawRequest>) {
+        let mut messages = vec![];
+        let mut shell = Shell::new(&mut messages);
+
+        let status = widget.on_event(
+            tree,
+            event,
+            Layout::new(node),
+            cursor,
+            renderer,
+            &mut clipboard::Null,
+            &mut shell,
+            &node.bounds(),
+        );
+
+        let redraw_request = shell.redraw_request();
+
+        (status, messages, redraw_request)
+    }
+
+    fn operate<Message, Theme, Renderer: core::Renderer, T>(
+        widget: &dyn Widget<Message, Theme, Renderer>,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        node: &layout::Node,
+        operation: &mut dyn Operation<T>,
+    ) {
+        widget.operate(
+            tree,
+            Layout::new(node),
+            renderer,
+            &mut operation::black_box(operation),
+        );
+    }
+
+    #[test]
+    fn stale_contents_are_drawn_while_a_rebuild_is_deferred() {
+        let sizes = Rc::new(RefCell::new(Vec::new()));
+
+        let mut responsive: Responsive<'_, (), (), Recorder> =
+            Responsive::new({
+                let sizes = sizes.clone();
+
+                move |size| {
+                    sizes.borrow_mut().push(size);
+
+                    Element::new(horizontal_space())
+                }
+            })
+            .frame_budget(Duration::ZERO)
+            .stretch_mode(StretchMode::Scale);
+
+        let mut tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+        let mut renderer = Recorder::default();
+
+        let small = layout::Node::new(Size::new(100.0, 100.0));
+        let large = layout::Node::new(Size::new(200.0, 200.0));
+
+        draw(&responsive, &tree, &mut renderer, &small);
+        assert_eq!(*sizes.borrow(), [Size::new(100.0, 100.0)]);
+
+        // The frame has already taken longer than the budget
+        let (_, _, redraw_request) = on_event(
+            &mut responsive,
+            &mut tree,
+            &renderer,
+            &large,
+            Event::Window(window::Event::RedrawRequested(
+                Instant::now() - Duration::from_millis(1),
+            )),
+            mouse::Cursor::Unavailable,
+        );
+
+        assert_eq!(redraw_request, Some(window::RedrawRequest::NextFrame));
+
+        draw(&responsive, &tree, &mut renderer, &large);
+        assert_eq!(*sizes.borrow(), [Size::new(100.0, 100.0)]);
+        assert_eq!(renderer.transformations, 1);
+
+        // A rebuild is never deferred for two frames in a row
+        let _ = on_event(
+            &mut responsive,
+            &mut tree,
+            &renderer,
+            &large,
+            Event::Window(window::Event::RedrawRequested(
+                Instant::now() - Duration::from_millis(1),
+            )),
+            mouse::Cursor::Unavailable,
+        );
+
+        draw(&responsive, &tree, &mut renderer, &large);
+        assert_eq!(
+            *sizes.borrow(),
+            [Size::new(100.0, 100.0), Size::new(200.0, 200.0)]
+        );
+        assert_eq!(renderer.transformations, 1);
+    }
+
+    #[test]
+    fn escape_only_dismisses_the_overlay() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Message {
+            Selected,
+            Closed,
+            Pressed,
+        }
+
+        let mut responsive: Responsive<'_, Message, crate::Theme, ()> =
+            Responsive::new(|_size| {
+                crate::Column::new()
+                    .push(
+                        crate::pick_list(["A", "B"], None::<&str>, |_| {
+                            Message::Selected
+                        })
+                        .on_close(Message::Closed),
+                    )
+                    .push(
+                        crate::button(crate::text("Button"))
+                            .on_press(Message::Pressed),
+                    )
+                    .into()
+            })
+            .close_overlay_on_escape(true);
+
+        let mut tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+        let node = layout::Node::new(Size::new(200.0, 200.0));
+
+        let is_overlay_open = |tree: &Tree| {
+            tree.state.downcast_ref::<State>().is_overlay_open.get()
+        };
+
+        // Open the pick list
+        let _ = on_event(
+            &mut responsive,
+            &mut tree,
+            &(),
+            &node,
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+            mouse::Cursor::Available(Point::new(1.0, 1.0)),
+        );
+
+        {
+            let mut overlay = responsive
+                .overlay(&mut tree, Layout::new(&node), &(), Vector::ZERO)
+                .expect("Responsive overlay");
+
+            let overlay_node = overlay.layout(&(), node.size());
+
+            let mut messages = vec![];
+            let mut shell = Shell::new(&mut messages);
+
+            let status = overlay.on_event(
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                    modified_key: keyboard::Key::Named(
+                        keyboard::key::Named::Escape,
+                    ),
+                    physical_key: keyboard::key::Physical::Code(
+                        keyboard::key::Code::Escape,
+                    ),
+                    location: keyboard::Location::Standard,
+                    modifiers: keyboard::Modifiers::default(),
+                    text: None,
+                    repeat: false,
+                }),
+                Layout::new(&overlay_node),
+                mouse::Cursor::Unavailable,
+                &(),
+                &mut clipboard::Null,
+                &mut shell,
+            );
+
+            assert_eq!(status, event::Status::Captured);
+            assert!(shell.is_layout_invalid());
+            assert!(messages.is_empty());
+        }
+
+        assert!(is_overlay_open(&tree));
+        assert!(responsive
+            .overlay(&mut tree, Layout::new(&node), &(), Vector::ZERO)
+            .is_none());
+        assert!(!is_overlay_open(&tree));
+
+        let button = responsive
+            .content
+            .borrow()
+            .layout
+            .as_ref()
+            .unwrap()
+            .children()[1]
+            .bounds()
+            .center();
+
+        // The next press closes the pick list, like a click outside of it,
+        // and is swallowed by it
+        let (status, messages, _) = on_event(
+            &mut responsive,
+            &mut tree,
+            &(),
+            &node,
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+            mouse::Cursor::Available(button),
+        );
+
+        assert_eq!(status, event::Status::Captured);
+        assert_eq!(messages, [Message::Closed]);
+
+        // ...but it still reaches the rest of the contents
+        let (_, messages, _) = on_event(
+            &mut responsive,
+            &mut tree,
+            &(),
+            &node,
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)),
+            mouse::Cursor::Available(button),
+        );
+
+        assert_eq!(messages, [Message::Pressed]);
+
+        assert!(responsive
+            .overlay(&mut tree, Layout::new(&node), &(), Vector::ZERO)
+            .is_some());
+        assert!(!is_overlay_open(&tree));
+    }
+
+    #[test]
+    fn measured_contents_are_rebuilt_only_when_the_decision_changes() {
+        let views = Rc::new(Cell::new(0));
+
+        let responsive: Responsive<'_, (), (), ()> =
+            Responsive::measured(|size| size.width < 150.0, {
+                let views = views.clone();
+
+                move |_is_narrow: &bool| {
+                    views.set(views.get() + 1);
+
+                    Element::new(horizontal_space())
+                }
+            });
+
+        let tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+
+        for (width, views_expected) in
+            [(100.0, 1), (120.0, 1), (140.0, 1), (200.0, 2), (300.0, 2)]
+        {
+            draw(
+                &responsive,
+                &tree,
+                &mut (),
+                &layout::Node::new(Size::new(width, 100.0)),
+            );
+
+            assert_eq!(views.get(), views_expected);
+        }
+    }
+
+    #[test]
+    fn nested_widgets_inherit_the_depth_limit() {
+        fn recursive(builds: Rc<Cell<usize>>) -> Element<'static, (), (), ()> {
+            Element::new(Responsive::new(move |_size| {
+                builds.set(builds.get() + 1);
+
+                recursive(builds.clone())
+            }))
+        }
+
+        let builds = Rc::new(Cell::new(0));
+
+        let responsive: Responsive<'_, (), (), ()> = Responsive::new({
+            let builds = builds.clone();
+
+            move |_size| {
+                builds.set(builds.get() + 1);
+
+                recursive(builds.clone())
+            }
+        })
+        .limit_depth(3);
+
+        let tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+
+        draw(
+            &responsive,
+            &tree,
+            &mut (),
+            &layout::Node::new(Size::new(100.0, 100.0)),
+        );
+
+        assert_eq!(builds.get(), 3);
+    }
+
+    #[test]
+    fn measurement_cache_evicts_the_oldest_measurement_when_full() {
+        let cache = MeasurementCache::new();
+        let now = Instant::now();
+
+        for i in 0..MeasurementCache::CAPACITY {
+            cache.insert(
+                (i as u64, 0),
+                1.0,
+                now + Duration::from_millis(i as u64),
+            );
+        }
+
+        let later = now + Duration::from_secs(60);
+        cache.insert((u64::MAX, 0), 2.0, later);
+
+        assert_eq!(cache.heights.borrow().len(), MeasurementCache::CAPACITY);
+        assert_eq!(cache.get((0, 0), later), None);
+        assert_eq!(cache.get((1, 0), later), Some(1.0));
+        assert_eq!(cache.get((u64::MAX, 0), later), Some(2.0));
+    }
+
+    #[test]
+    fn retained_children_keep_their_state_when_moved() {
+        let responsive: Responsive<'_, (), crate::Theme, ()> =
+            Responsive::new(|size| {
+                let input =
+                    retained("form", crate::text_input("Name", "").id("name"));
+
+                if size.width < 150.0 {
+                    crate::Column::new().push(input).into()
+                } else {
+                    crate::Column::new()
+                        .push(horizontal_space())
+                        .push(input)
+                        .into()
+                }
+            })
+            .retain([widget::Id::new("form")]);
+
+        let mut tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+
+        operate(
+            &responsive,
+            &mut tree,
+            &(),
+            &layout::Node::new(Size::new(100.0, 100.0)),
+            &mut operation::focusable::focus::<()>(widget::Id::new("name")),
+        );
+
+        let mut find_focused = operation::focusable::find_focused();
+
+        operate(
+            &responsive,
+            &mut tree,
+            &(),
+            &layout::Node::new(Size::new(200.0, 100.0)),
+            &mut find_focused,
+        );
+
+        assert!(matches!(
+            find_focused.finish(),
+            operation::Outcome::Some(id) if id == widget::Id::new("name")
+        ));
+    }
+
+    #[test]
+    fn scroll_offset_is_preserved_across_rebuilds() {
+        let responsive: Responsive<'_, (), crate::Theme, ()> =
+            Responsive::new(|size| {
+                let list =
+                    crate::scrollable(crate::vertical_space().height(1000))
+                        .id(crate::scrollable::Id::new("list"));
+
+                if size.width < 150.0 {
+                    list.into()
+                } else {
+                    crate::container(list).into()
+                }
+            })
+            .preserve_scroll(crate::scrollable::Id::new("list"));
+
+        let mut tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+
+        operate(
+            &responsive,
+            &mut tree,
+            &(),
+            &layout::Node::new(Size::new(100.0, 100.0)),
+            &mut scrollable::snap_to::<()>(
+                widget::Id::new("list"),
+                RelativeOffset { x: 0.0, y: 0.5 },
+            ),
+        );
+
+        let mut scroll_offset = ScrollOffset {
+            target: widget::Id::new("list"),
+            offset: None,
+        };
+
+        operate(
+            &responsive,
+            &mut tree,
+            &(),
+            &layout::Node::new(Size::new(200.0, 100.0)),
+            &mut scroll_offset,
+        );
+
+        assert_eq!(
+            scroll_offset.offset,
+            Some(RelativeOffset { x: 0.0, y: 0.5 })
+        );
+    }
+
+    #[test]
+    fn contents_are_frozen_during_an_interaction() {
+        let sizes = Rc::new(RefCell::new(Vec::new()));
+
+        let mut responsive: Responsive<'_, (), crate::Theme, ()> =
+            Responsive::new({
+                let sizes = sizes.clone();
+
+                move |size| {
+                    sizes.borrow_mut().push(size);
+
+                    crate::button(horizontal_space())
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .on_press(())
+                        .into()
+                }
+            });
+
+        let mut tree = Tree::new(&responsive as &dyn Widget<_, _, _>);
+
+        let small = layout::Node::new(Size::new(100.0, 100.0));
+        let large = layout::Node::new(Size::new(200.0, 200.0));
+        let cursor = mouse::Cursor::Available(Point::new(1.0, 1.0));
+
+        let mut send = |node: &layout::Node, event: Event| {
+            let _ =
+                on_event(&mut responsive, &mut tree, &(), node, event, cursor);
+        };
+
+        for end in [
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)),
+            Event::Mouse(mouse::Event::CursorLeft),
+            Event::Window(window::Event::Unfocused),
+        ] {
+            sizes.borrow_mut().clear();
+
+            send(
+                &small,
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+            );
+            send(
+                &large,
+                Event::Mouse(mouse::Event::CursorMoved {
+                    position: Point::new(1.0, 1.0),
+                }),
+            );
+            assert_eq!(*sizes.borrow(), [Size::new(100.0, 100.0)]);
+
+            send(&large, end);
+            assert_eq!(*sizes.borrow(), [Size::new(100.0, 100.0)]);
+
+            send(
+                &large,
+                Event::Mouse(mouse::Event::CursorMoved {
+                    position: Point::new(1.0, 1.0),
+                }),
+            );
+            assert_eq!(
+                *sizes.borrow(),
+                [Size::new(100.0, 100.0), Size::new(200.0, 200.0)]
+            );
+        }
+    }
+}
@@ -45,6 +45,9 @@ mod lazy;
 #[cfg(feature = "lazy")]
 pub use crate::lazy::helpers::*;
 
+#[cfg(feature = "lazy")]
+pub use crate::lazy::responsive;
+
 #[doc(no_inline)]
 pub use button::Button;
 #[doc(no_inline)]
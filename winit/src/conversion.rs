@@ -17,11 +17,23 @@ pub fn window_attributes(
 ) -> winit::window::WindowAttributes {
     let mut attributes = winit::window::WindowAttributes::default();
 
+    let size = settings
+        .monitor_fraction
+        .filter(|fraction| *fraction > 0.0 && *fraction <= 1.0)
+        .zip(primary_monitor.as_ref())
+        .map(|(fraction, monitor)| {
+            let resolution: winit::dpi::LogicalSize<f32> =
+                monitor.size().to_logical(monitor.scale_factor());
+
+            Size::new(resolution.width, resolution.height) * fraction
+        })
+        .unwrap_or(settings.size);
+
     attributes = attributes
         .with_title(title)
         .with_inner_size(winit::dpi::LogicalSize {
-            width: settings.size.width,
-            height: settings.size.height,
+            width: size.width,
+            height: size.height,
         })
         .with_resizable(settings.resizable)
         .with_enabled_buttons(if settings.resizable {
@@ -37,7 +49,7 @@ pub fn window_attributes(
         .with_visible(settings.visible);
 
     if let Some(position) =
-        position(primary_monitor.as_ref(), settings.size, settings.position)
+        position(primary_monitor.as_ref(), size, settings.position)
     {
         attributes = attributes.with_position(position);
     }
@@ -227,6 +239,7 @@ pub fn window_event(
                 location,
                 logical_key,
                 physical_key,
+                repeat,
                 ..
             } = event;
 
@@ -257,6 +270,7 @@ pub fn window_event(
                         modifiers,
                         location,
                         text,
+                        repeat,
                     }
                 }
                 winit::event::ElementState::Released => {
@@ -278,6 +292,9 @@ pub fn window_event(
         } else {
             window::Event::Unfocused
         })),
+        WindowEvent::Occluded(occluded) => {
+            Some(Event::Window(window::Event::Occluded(occluded)))
+        }
         WindowEvent::HoveredFile(path) => {
             Some(Event::Window(window::Event::FileHovered(path.clone())))
         }
@@ -400,6 +417,20 @@ pub fn fullscreen(
     }
 }
 
+/// Converts a [`winit`] monitor handle to a [`window::Monitor`].
+pub fn monitor(monitor: &winit::monitor::MonitorHandle) -> window::Monitor {
+    let position = monitor.position();
+    let size = monitor.size();
+
+    window::Monitor {
+        name: monitor.name(),
+        position: Point::new(position.x as f32, position.y as f32),
+        size: Size::new(size.width as f32, size.height as f32),
+        scale_factor: monitor.scale_factor() as f32,
+        refresh_rate: monitor.refresh_rate_millihertz(),
+    }
+}
+
 /// Converts a [`window::Mode`] to a visibility flag.
 pub fn visible(mode: window::Mode) -> bool {
     match mode {
@@ -508,11 +508,14 @@ where
                                 settings,
                                 title,
                                 monitor,
+                                parent,
                                 on_open,
                             } => {
                                 let exit_on_close_request =
                                     settings.exit_on_close_request;
 
+                                let parent_id = settings.parent;
+
                                 let visible = settings.visible;
 
                                 #[cfg(target_arch = "wasm32")]
@@ -524,11 +527,23 @@ where
                                         settings,
                                         &title,
                                         monitor
-                                            .or(event_loop.primary_monitor()),
+                                            .or_else(|| {
+                                                event_loop.primary_monitor()
+                                            })
+                                            .or_else(|| {
+                                                event_loop
+                                                    .available_monitors()
+                                                    .next()
+                                            }),
                                         self.id.clone(),
                                     )
                                     .with_visible(false);
 
+                                let window_attributes = with_parent(
+                                    window_attributes,
+                                    parent.as_deref(),
+                                );
+
                                 #[cfg(target_arch = "wasm32")]
                                 let window_attributes = {
                                     use winit::platform::web::WindowAttributesExtWebSys;
@@ -595,6 +610,7 @@ where
                                         window,
                                         exit_on_close_request,
                                         make_visible: visible,
+                                        parent: parent_id,
                                         on_open,
                                     },
                                 );
@@ -644,6 +660,7 @@ enum Event<Message: 'static> {
         window: winit::window::Window,
         exit_on_close_request: bool,
         make_visible: bool,
+        parent: Option<window::Id>,
         on_open: oneshot::Sender<window::Id>,
     },
     EventLoopAwakened(winit::event::Event<Message>),
@@ -658,6 +675,7 @@ enum Control {
         settings: window::Settings,
         title: String,
         monitor: Option<winit::monitor::MonitorHandle>,
+        parent: Option<Arc<winit::window::Window>>,
         on_open: oneshot::Sender<window::Id>,
     },
 }
@@ -712,14 +730,33 @@ async fn run_instance<P, C>(
                 window,
                 exit_on_close_request,
                 make_visible,
+                parent,
                 on_open,
             } => {
+                // The parent may have been closed while its child was being
+                // created; in that case, the child is closed right away, so
+                // no window outlives its parent.
+                if parent
+                    .is_some_and(|parent| window_manager.get(parent).is_none())
+                {
+                    log::warn!(
+                        "Parent of window `{id:?}` was closed before the \
+                        window could be opened"
+                    );
+
+                    drop(window);
+                    is_window_opening = false;
+
+                    continue;
+                }
+
                 let window = window_manager.insert(
                     id,
                     Arc::new(window),
                     &program,
                     &mut compositor,
                     exit_on_close_request,
+                    parent,
                 );
 
                 let logical_size = window.state.logical_size();
@@ -807,6 +844,12 @@ async fn run_instance<P, C>(
                             continue;
                         };
 
+                        // Occluded windows are not visible; stop drawing
+                        // and requesting frames until they are shown again.
+                        if window.state.is_occluded() {
+                            continue;
+                        }
+
                         // TODO: Avoid redrawing all the time by forcing widgets to
                         // request redraws on state changes
                         //
@@ -1015,6 +1058,14 @@ async fn run_instance<P, C>(
                                 &mut is_window_opening,
                             );
                         } else {
+                            if let (
+                                winit::event::WindowEvent::Resized(size),
+                                Some(ratio),
+                            ) = (&window_event, window.aspect_ratio)
+                            {
+                                lock_aspect_ratio(&window.raw, *size, ratio);
+                            }
+
                             window.state.update(
                                 &window.raw,
                                 &window_event,
@@ -1236,6 +1287,10 @@ fn run_action<P, C>(
         Action::Window(action) => match action {
             window::Action::Open(id, settings, channel) => {
                 let monitor = window_manager.last_monitor();
+                let parent = settings
+                    .parent
+                    .and_then(|parent| window_manager.get(parent))
+                    .map(|parent| parent.raw.clone());
 
                 control_sender
                     .start_send(Control::CreateWindow {
@@ -1243,6 +1298,7 @@ fn run_action<P, C>(
                         settings,
                         title: program.title(id),
                         monitor,
+                        parent,
                         on_open: channel,
                     })
                     .expect("Send control action");
@@ -1250,6 +1306,24 @@ fn run_action<P, C>(
                 *is_window_opening = true;
             }
             window::Action::Close(id) => {
+                // Children are closed first, so no window outlives its parent.
+                for child in window_manager.children(id) {
+                    run_action(
+                        Action::Window(window::Action::Close(child)),
+                        program,
+                        compositor,
+                        events,
+                        messages,
+                        clipboard,
+                        control_sender,
+                        debug,
+                        interfaces,
+                        window_manager,
+                        ui_caches,
+                        is_window_opening,
+                    );
+                }
+
                 let _ = ui_caches.remove(&id);
                 let _ = interfaces.remove(&id);
 
@@ -1367,6 +1441,34 @@ fn run_action<P, C>(
                     ));
                 }
             }
+            window::Action::GetMonitors(id, channel) => {
+                if let Some(window) = window_manager.get_mut(id) {
+                    let monitors = window
+                        .raw
+                        .available_monitors()
+                        .map(|monitor| conversion::monitor(&monitor))
+                        .collect();
+
+                    let _ = channel.send(monitors);
+                }
+            }
+            window::Action::FullscreenOn(id, name) => {
+                if let Some(window) = window_manager.get_mut(id) {
+                    let monitor = name
+                        .and_then(|name| {
+                            window.raw.available_monitors().find(|monitor| {
+                                monitor.name().as_deref() == Some(name.as_str())
+                            })
+                        })
+                        .or_else(|| window.raw.current_monitor());
+
+                    window.raw.set_visible(true);
+                    window.raw.set_fullscreen(conversion::fullscreen(
+                        monitor,
+                        core::window::Mode::Fullscreen,
+                    ));
+                }
+            }
             window::Action::ChangeIcon(id, icon) => {
                 if let Some(window) = window_manager.get_mut(id) {
                     window.raw.set_window_icon(conversion::icon(icon));
@@ -1383,6 +1485,20 @@ fn run_action<P, C>(
                     let _ = channel.send(mode);
                 }
             }
+            window::Action::LockAspectRatio(id, ratio) => {
+                if let Some(window) = window_manager.get_mut(id) {
+                    window.aspect_ratio =
+                        ratio.filter(|ratio| ratio.is_finite() && *ratio > 0.0);
+
+                    if let Some(ratio) = window.aspect_ratio {
+                        lock_aspect_ratio(
+                            &window.raw,
+                            window.raw.inner_size(),
+                            ratio,
+                        );
+                    }
+                }
+            }
             window::Action::ToggleMaximize(id) => {
                 if let Some(window) = window_manager.get_mut(id) {
                     window.raw.set_maximized(!window.raw.is_maximized());
@@ -1426,6 +1542,14 @@ fn run_action<P, C>(
                     }
                 }
             }
+            window::Action::ShowSystemMenuAt(id, position) => {
+                if let Some(window) = window_manager.get_mut(id) {
+                    window.raw.show_window_menu(winit::dpi::LogicalPosition {
+                        x: position.x,
+                        y: position.y,
+                    });
+                }
+            }
             window::Action::GetRawId(id, channel) => {
                 if let Some(window) = window_manager.get_mut(id) {
                     let _ = channel.send(window.raw.id().into());
@@ -1441,6 +1565,16 @@ fn run_action<P, C>(
                     f(handle);
                 }
             }
+            window::Action::RunWithDisplayHandle(id, f) => {
+                use window::raw_window_handle::HasDisplayHandle;
+
+                if let Some(handle) = window_manager
+                    .get_mut(id)
+                    .and_then(|window| window.raw.display_handle().ok())
+                {
+                    f(handle);
+                }
+            }
             window::Action::Screenshot(id, channel) => {
                 if let Some(window) = window_manager.get_mut(id) {
                     let bytes = compositor.screenshot(
@@ -1548,6 +1682,26 @@ where
         .collect()
 }
 
+/// Sets the parent of the given window attributes, if any.
+#[allow(unsafe_code)]
+fn with_parent(
+    attributes: winit::window::WindowAttributes,
+    parent: Option<&winit::window::Window>,
+) -> winit::window::WindowAttributes {
+    use runtime::window::raw_window_handle::HasWindowHandle;
+
+    let Some(handle) = parent.and_then(|parent| parent.window_handle().ok())
+    else {
+        return attributes;
+    };
+
+    // SAFETY: The parent window outlives its children, since we hold
+    // a reference to it while the child is created, a child whose parent
+    // was closed in the meantime is closed as soon as it is created, and
+    // children are closed before their parent is removed and destroyed.
+    unsafe { attributes.with_parent_window(Some(handle.as_raw())) }
+}
+
 /// Returns true if the provided event should cause a [`Program`] to
 /// exit.
 pub fn user_force_quit(
@@ -1568,3 +1722,45 @@ pub fn user_force_quit(
         _ => false,
     }
 }
+
+/// Requests the largest size that fits in the given `size` and has the given
+/// aspect `ratio`, unless the window already has it.
+///
+/// The minimum and maximum sizes of the window are enforced by [`winit`]
+/// when the request is applied. Maximized and fullscreen windows are left
+/// alone, since their size is dictated by the window manager; and so are
+/// windows with no area, like minimized ones on some platforms.
+fn lock_aspect_ratio(
+    window: &winit::window::Window,
+    size: winit::dpi::PhysicalSize<u32>,
+    ratio: f32,
+) {
+    if size.width == 0
+        || size.height == 0
+        || window.is_maximized()
+        || window.fullscreen().is_some()
+    {
+        return;
+    }
+
+    let width = size.width as f32;
+    let height = size.height as f32;
+
+    let locked = if width / height > ratio {
+        winit::dpi::PhysicalSize::new(
+            (height * ratio).round() as u32,
+            size.height,
+        )
+    } else {
+        winit::dpi::PhysicalSize::new(
+            size.width,
+            (width / ratio).round() as u32,
+        )
+    };
+
+    if locked.width.abs_diff(size.width) > 1
+        || locked.height.abs_diff(size.height) > 1
+    {
+        let _ = window.request_inner_size(locked);
+    }
+}
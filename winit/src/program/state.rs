@@ -19,6 +19,7 @@ where
     viewport_version: u64,
     cursor_position: Option<winit::dpi::PhysicalPosition<f64>>,
     modifiers: winit::keyboard::ModifiersState,
+    is_occluded: bool,
     theme: P::Theme,
     appearance: program::Appearance,
 }
@@ -34,6 +35,7 @@ where
             .field("viewport", &self.viewport)
             .field("viewport_version", &self.viewport_version)
             .field("cursor_position", &self.cursor_position)
+            .field("is_occluded", &self.is_occluded)
             .field("appearance", &self.appearance)
             .finish()
     }
@@ -70,6 +72,7 @@ where
             viewport_version: 0,
             cursor_position: None,
             modifiers: winit::keyboard::ModifiersState::default(),
+            is_occluded: false,
             theme,
             appearance,
         }
@@ -120,6 +123,11 @@ where
         self.modifiers
     }
 
+    /// Returns whether the window of the [`State`] is currently occluded.
+    pub fn is_occluded(&self) -> bool {
+        self.is_occluded
+    }
+
     /// Returns the current theme of the [`State`].
     pub fn theme(&self) -> &P::Theme {
         &self.theme
@@ -178,6 +186,9 @@ where
             WindowEvent::ModifiersChanged(new_modifiers) => {
                 self.modifiers = new_modifiers.state();
             }
+            WindowEvent::Occluded(is_occluded) => {
+                self.is_occluded = *is_occluded;
+            }
             #[cfg(feature = "debug")]
             WindowEvent::KeyboardInput {
                 event:
@@ -39,6 +39,7 @@ where
         application: &P,
         compositor: &mut C,
         exit_on_close_request: bool,
+        parent: Option<Id>,
     ) -> &mut Window<P, C> {
         let state = State::new(application, id, &window);
         let viewport_version = state.viewport_version();
@@ -59,9 +60,11 @@ where
                 state,
                 viewport_version,
                 exit_on_close_request,
+                parent,
                 surface,
                 renderer,
                 mouse_interaction: mouse::Interaction::None,
+                aspect_ratio: None,
             },
         );
 
@@ -101,6 +104,14 @@ where
         Some((id, self.get_mut(id)?))
     }
 
+    pub fn children(&self, id: Id) -> Vec<Id> {
+        self.entries
+            .iter()
+            .filter(|(_, window)| window.parent == Some(id))
+            .map(|(child, _)| *child)
+            .collect()
+    }
+
     pub fn last_monitor(&self) -> Option<MonitorHandle> {
         self.entries.values().last()?.raw.current_monitor()
     }
@@ -135,9 +146,11 @@ where
     pub state: State<P>,
     pub viewport_version: u64,
     pub exit_on_close_request: bool,
+    pub parent: Option<Id>,
     pub mouse_interaction: mouse::Interaction,
     pub surface: C::Surface,
     pub renderer: P::Renderer,
+    pub aspect_ratio: Option<f32>,
 }
 
 impl<P, C> Window<P, C>